@@ -0,0 +1,284 @@
+use crate::{
+    command::{async_trait, catch_up::CatchUp, help, hours::Hours, Command, Ctx, Response},
+    locale::{tr, trf, Language},
+    prelude::*,
+    settings::{self, RoomSettings},
+};
+
+/// `!botto` shows the help text, `!botto <setting> <value>` changes the room's settings.
+pub struct Botto;
+
+pub enum Request {
+    Help,
+    Prefix(Option<String>),
+    Replies(Option<bool>),
+    Language(Option<Language>),
+    /// What happens to reminders missed while the bot was offline.
+    CatchUp(Option<CatchUp>),
+    /// Hours in which reminders at random times aren't sent, `Some(None)` turns them off.
+    QuietHours(Option<Option<Hours>>),
+    /// Enables or disables the named command.
+    Enabled(String, bool),
+}
+
+#[async_trait]
+impl Command for Botto {
+    type Args = Request;
+
+    fn name(&self) -> &'static str {
+        "botto"
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &'static str {
+        "ℹ️ - help and settings"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &[
+            "botto",
+            "botto prefix ?",
+            "botto replies off",
+            "botto language de",
+            "botto catchup grace 2h",
+            "botto quiet 22:00-7:00",
+            "botto disable rps",
+            "botto enable rps",
+        ]
+    }
+
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String> {
+        let (setting, value) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        let value = Some(value.trim()).filter(|v| !v.is_empty());
+
+        match setting.to_lowercase().as_str() {
+            "" => Ok(Request::Help),
+            "prefix" => Ok(Request::Prefix(value.map(str::to_string))),
+            "replies" => match value {
+                None => Ok(Request::Replies(None)),
+                Some(value) => settings::parse_toggle(value)
+                    .map(|on| Request::Replies(Some(on)))
+                    .ok_or_else(|| tr(lang, "settings.toggle_invalid").to_string()),
+            },
+            "language" | "sprache" => match value {
+                None => Ok(Request::Language(None)),
+                Some(value) => value
+                    .parse()
+                    .map(|l| Request::Language(Some(l)))
+                    .map_err(|_| {
+                        let languages = Language::ALL.map(|l| l.code()).join(", ");
+                        trf(
+                            lang,
+                            "settings.language_invalid",
+                            &[("languages", &languages)],
+                        )
+                    }),
+            },
+            "catchup" | "nachholen" => match value {
+                None => Ok(Request::CatchUp(None)),
+                Some(value) => CatchUp::parse(value)
+                    .map(|c| Request::CatchUp(Some(c)))
+                    .ok_or_else(|| tr(lang, "settings.catch_up_invalid").to_string()),
+            },
+            "quiet" | "ruhezeit" => match value {
+                None => Ok(Request::QuietHours(None)),
+                Some(value) if settings::parse_toggle(value) == Some(false) => {
+                    Ok(Request::QuietHours(Some(None)))
+                }
+                Some(value) => Hours::parse(value)
+                    .map(|h| Request::QuietHours(Some(Some(h))))
+                    .ok_or_else(|| tr(lang, "settings.quiet_invalid").to_string()),
+            },
+            "enable" | "disable" => {
+                let Some(command) = value else {
+                    return Err(tr(lang, "settings.which_command").to_string());
+                };
+                let command = command.to_lowercase();
+
+                Ok(Request::Enabled(
+                    command,
+                    setting.eq_ignore_ascii_case("enable"),
+                ))
+            }
+            _ => Err(trf(lang, "settings.unknown", &[("setting", &setting)])),
+        }
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, args: Self::Args) -> Result<Option<Response>> {
+        let room_id = ctx.room.room_id();
+
+        let changes_settings = !matches!(
+            args,
+            Request::Help
+                | Request::Prefix(None)
+                | Request::Replies(None)
+                | Request::Language(None)
+                | Request::CatchUp(None)
+                | Request::QuietHours(None)
+        );
+        if changes_settings && !settings::may_change(ctx.room, ctx.sender).await? {
+            info!(
+                "⚙️ {} may not change settings of room {room_id}",
+                ctx.sender
+            );
+            return Ok(Some(tr(ctx.lang, "settings.forbidden").into()));
+        }
+
+        let resp = match args {
+            Request::Help => {
+                return Ok(Some(help::text(
+                    ctx.registry,
+                    ctx.prefix,
+                    &ctx.settings.disabled_commands,
+                    ctx.lang,
+                )))
+            }
+            Request::Prefix(None) => trf(
+                ctx.lang,
+                "settings.prefix_current",
+                &[("prefix", &ctx.prefix)],
+            ),
+            Request::Prefix(Some(prefix)) => {
+                if !settings::is_valid_prefix(&prefix) {
+                    return Ok(Some(Response::plain(trf(
+                        ctx.lang,
+                        "settings.prefix_invalid",
+                        &[("max", &settings::MAX_PREFIX_LEN)],
+                    ))));
+                }
+
+                let mut room_settings = ctx.settings.clone();
+                // the deployment's default is not stored, so the room follows later changes to it
+                room_settings.prefix = Some(prefix.clone()).filter(|p| *p != ctx.config.prefix);
+                room_settings.save(room_id, ctx.db).await?;
+
+                trf(ctx.lang, "settings.prefix_set", &[("prefix", &prefix)])
+            }
+            Request::Replies(None) => tr(
+                ctx.lang,
+                if ctx.settings.replies() {
+                    "settings.replies_on"
+                } else {
+                    "settings.replies_off"
+                },
+            )
+            .to_string(),
+            Request::Replies(Some(on)) => {
+                let mut room_settings = ctx.settings.clone();
+                room_settings.replies = Some(on);
+                room_settings.save(room_id, ctx.db).await?;
+
+                tr(
+                    ctx.lang,
+                    if on {
+                        "settings.replies_turned_on"
+                    } else {
+                        "settings.replies_turned_off"
+                    },
+                )
+                .to_string()
+            }
+            Request::Language(None) => trf(
+                ctx.lang,
+                "settings.language_current",
+                &[("prefix", &ctx.prefix)],
+            ),
+            Request::Language(Some(lang)) => {
+                let mut room_settings = ctx.settings.clone();
+                room_settings.language = Some(lang).filter(|l| *l != ctx.config.language);
+                room_settings.save(room_id, ctx.db).await?;
+
+                // confirmed in the new language
+                tr(lang, "settings.language_set").to_string()
+            }
+            Request::CatchUp(None) => trf(
+                ctx.lang,
+                "settings.catch_up_current",
+                &[
+                    (
+                        "policy",
+                        &ctx.settings.catch_up.unwrap_or_default().describe(ctx.lang),
+                    ),
+                    ("prefix", &ctx.prefix),
+                ],
+            ),
+            Request::CatchUp(Some(catch_up)) => {
+                let mut room_settings = ctx.settings.clone();
+                room_settings.catch_up = Some(catch_up).filter(|c| *c != CatchUp::default());
+                room_settings.save(room_id, ctx.db).await?;
+
+                trf(
+                    ctx.lang,
+                    "settings.catch_up_set",
+                    &[("policy", &catch_up.describe(ctx.lang))],
+                )
+            }
+            Request::QuietHours(None) => match ctx.settings.quiet_hours {
+                Some(hours) => trf(
+                    ctx.lang,
+                    "settings.quiet_current",
+                    &[
+                        ("hours", &hours.describe(ctx.lang)),
+                        ("prefix", &ctx.prefix),
+                    ],
+                ),
+                None => trf(ctx.lang, "settings.quiet_none", &[("prefix", &ctx.prefix)]),
+            },
+            Request::QuietHours(Some(hours)) => {
+                let mut room_settings = ctx.settings.clone();
+                room_settings.quiet_hours = hours;
+                room_settings.save(room_id, ctx.db).await?;
+
+                match hours {
+                    Some(hours) => trf(
+                        ctx.lang,
+                        "settings.quiet_set",
+                        &[("hours", &hours.describe(ctx.lang))],
+                    ),
+                    None => tr(ctx.lang, "settings.quiet_off").to_string(),
+                }
+            }
+            Request::Enabled(command, enable) => {
+                let Some(command) = ctx.registry.find(&command).map(|c| c.name()) else {
+                    return Ok(Some(
+                        trf(
+                            ctx.lang,
+                            "settings.unknown_command",
+                            &[("command", &command)],
+                        )
+                        .into(),
+                    ));
+                };
+                if command == self.name() {
+                    let command = format!("{}{command}", ctx.prefix);
+                    return Ok(Some(
+                        trf(
+                            ctx.lang,
+                            "settings.not_disableable",
+                            &[("command", &command)],
+                        )
+                        .into(),
+                    ));
+                }
+
+                RoomSettings::set_enabled(room_id, command, enable, ctx.sender, ctx.db).await?;
+
+                let command = format!("{}{command}", ctx.prefix);
+                trf(
+                    ctx.lang,
+                    if enable {
+                        "settings.enabled"
+                    } else {
+                        "settings.disabled"
+                    },
+                    &[("command", &command)],
+                )
+            }
+        };
+
+        Ok(Some(resp.into()))
+    }
+}
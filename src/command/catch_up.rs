@@ -0,0 +1,175 @@
+use crate::{
+    command::span::Span,
+    locale::{tr, trf, Language},
+    prelude::*,
+};
+
+/// Reminders sent later than this, e.g. after the bot was offline, were missed.
+const LATE_AFTER: i64 = 60;
+
+const HOUR: u64 = 60 * 60;
+
+/// What happens to reminders that came due while the bot was offline, set per room.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum CatchUp {
+    /// Sent once with a note how late it is.
+    #[default]
+    Late,
+    /// Not sent, recurring reminders continue with their next time.
+    Skip,
+    /// Sent late within the window like [`CatchUp::Late`], skipped after it like [`CatchUp::Skip`].
+    Grace(Span),
+}
+
+/// How a due reminder is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Missed {
+    OnTime,
+    /// Sent with the rounded time it's late by.
+    Late(Span),
+    Skipped,
+}
+
+impl CatchUp {
+    /// Parses e.g. `late`, `skip` or `grace 2h`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        let (word, rest) = text.split_once(char::is_whitespace).unwrap_or((&text, ""));
+
+        match (word, rest.trim()) {
+            ("late" | "spät", "") => Some(Self::Late),
+            ("skip" | "überspringen", "") => Some(Self::Skip),
+            ("grace" | "frist", window) => match Span::parse_range(window)? {
+                // months have no fixed length
+                (false, window, None) if window.months == 0 => Some(Self::Grace(window)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// How the reminder due at `due` is handled at `now`.
+    pub fn missed(&self, due: DateTime<Utc>, now: DateTime<Utc>) -> Missed {
+        let late = (now - due).num_seconds();
+        if late <= LATE_AFTER {
+            return Missed::OnTime;
+        }
+        let late = late.unsigned_abs();
+
+        match self {
+            Self::Late => Missed::Late(rounded(late)),
+            Self::Grace(window) if late <= window.seconds => Missed::Late(rounded(late)),
+            Self::Skip | Self::Grace(_) => Missed::Skipped,
+        }
+    }
+
+    pub fn describe(&self, lang: Language) -> String {
+        match self {
+            Self::Late => tr(lang, "catch_up.late").to_string(),
+            Self::Skip => tr(lang, "catch_up.skip").to_string(),
+            Self::Grace(window) => trf(
+                lang,
+                "catch_up.grace",
+                &[("window", &window.describe(lang))],
+            ),
+        }
+    }
+}
+
+/// Minutes below an hour, hours above, e.g. `5 hours` instead of `5 hours 3 minutes 12 seconds`.
+fn rounded(seconds: u64) -> Span {
+    let unit = if seconds < HOUR { 60 } else { HOUR };
+    Span {
+        months: 0,
+        seconds: seconds / unit * unit,
+    }
+}
+
+impl TryFrom<String> for CatchUp {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.split_once(' ') {
+            Some(("grace", seconds)) => seconds
+                .parse()
+                .map(|seconds| Self::Grace(Span { months: 0, seconds }))
+                .map_err(|err| format!("invalid grace window {seconds}: {err}")),
+            _ => Self::parse(&value).ok_or_else(|| format!("invalid catch-up policy {value}")),
+        }
+    }
+}
+
+impl From<CatchUp> for String {
+    fn from(catch_up: CatchUp) -> Self {
+        match catch_up {
+            CatchUp::Late => "late".to_string(),
+            CatchUp::Skip => "skip".to_string(),
+            CatchUp::Grace(window) => format!("grace {}", window.seconds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_catch_up() {
+        let hours = |h: u64| Span {
+            months: 0,
+            seconds: h * HOUR,
+        };
+
+        assert_eq!(CatchUp::parse("late"), Some(CatchUp::Late));
+        assert_eq!(CatchUp::parse(" Skip "), Some(CatchUp::Skip));
+        assert_eq!(CatchUp::parse("grace 2h"), Some(CatchUp::Grace(hours(2))));
+        assert_eq!(
+            CatchUp::parse("frist 1 day"),
+            Some(CatchUp::Grace(hours(24)))
+        );
+        for text in ["", "grace", "grace 1 month", "grace 1-2h", "later"] {
+            assert_eq!(CatchUp::parse(text), None, "{text}");
+        }
+
+        let stored = String::from(CatchUp::Grace(hours(2)));
+        assert_eq!(stored, "grace 7200");
+        assert_eq!(CatchUp::try_from(stored), Ok(CatchUp::Grace(hours(2))));
+    }
+
+    #[test]
+    pub fn missed_reminders() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let due = at("2026-10-18T08:00:00Z");
+        let span = |seconds: u64| Span { months: 0, seconds };
+        let grace = CatchUp::Grace(span(2 * HOUR));
+
+        // the clock moves on while the bot is offline
+        let clock = [
+            (
+                "2026-10-18T08:00:30Z",
+                [Missed::OnTime, Missed::OnTime, Missed::OnTime],
+            ),
+            (
+                "2026-10-18T08:42:10Z",
+                [
+                    Missed::Late(span(42 * 60)),
+                    Missed::Skipped,
+                    Missed::Late(span(42 * 60)),
+                ],
+            ),
+            (
+                "2026-10-18T13:03:12Z",
+                [
+                    Missed::Late(span(5 * HOUR)),
+                    Missed::Skipped,
+                    Missed::Skipped,
+                ],
+            ),
+        ];
+        for (now, expected) in clock {
+            let missed = [CatchUp::Late, CatchUp::Skip, grace].map(|c| c.missed(due, at(now)));
+            assert_eq!(missed, expected, "{now}");
+        }
+    }
+}
@@ -1,14 +1,46 @@
-use crate::prelude::*;
+use crate::{
+    command::{async_trait, Command, Ctx, Response},
+    locale::{tr, Language},
+    prelude::*,
+};
 use rand::seq::SliceRandom;
 
-pub fn option(text: String) -> String {
+pub struct Choose;
+
+#[async_trait]
+impl Command for Choose {
+    type Args = String;
+
+    fn name(&self) -> &'static str {
+        "choose"
+    }
+
+    fn description(&self) -> &'static str {
+        "🔘 - choose an option"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["choose pizza, pasta, sushi"]
+    }
+
+    fn parse(&self, args: &str, _lang: Language) -> Result<Self::Args, String> {
+        Ok(args.to_string())
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, args: Self::Args) -> Result<Option<Response>> {
+        Ok(Some(option(args, ctx.lang).into()))
+    }
+}
+
+pub fn option(text: String, lang: Language) -> String {
     let options = text.split(',').map(str::trim).collect::<Vec<_>>();
     if options.len() < 2 {
-        return "Please provide at least two options.".into();
+        return tr(lang, "choose.too_few").into();
     }
     let choice = options
         .choose(&mut rand::thread_rng())
-        .unwrap_or(&"I have no idea.");
+        .copied()
+        .unwrap_or(tr(lang, "no_idea"));
 
     info!("🎰 choosing '{}' from {:?}", choice, options);
 
@@ -1,15 +1,46 @@
-use crate::prelude::*;
+use crate::{
+    command::{async_trait, Command, Ctx, Response},
+    locale::{tr, Language},
+    prelude::*,
+};
 use rand::seq::SliceRandom;
 
-pub fn flip() -> String {
+pub struct Coinflip;
+
+#[async_trait]
+impl Command for Coinflip {
+    type Args = ();
+
+    fn name(&self) -> &'static str {
+        "coinflip"
+    }
+
+    fn description(&self) -> &'static str {
+        "🪙 - coin flip"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["coinflip"]
+    }
+
+    fn parse(&self, _args: &str, _lang: Language) -> Result<Self::Args, String> {
+        Ok(())
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, _args: Self::Args) -> Result<Option<Response>> {
+        Ok(Some(flip(ctx.lang).into()))
+    }
+}
+
+pub fn flip(lang: Language) -> String {
     info!("🪙 coin flipping");
 
-    let answers = ["Heads", "Tails"];
+    let answers = [tr(lang, "coin.heads"), tr(lang, "coin.tails")];
     answers
         .choose(&mut rand::thread_rng())
         .unwrap_or({
             error!("fails to select answer");
-            &"I have no idea."
+            &tr(lang, "no_idea")
         })
         .to_string()
 }
@@ -1,27 +1,46 @@
-use crate::prelude::*;
+use crate::{
+    command::{async_trait, Command, Ctx, Response},
+    locale::{tr, Language},
+    prelude::*,
+};
 use rand::seq::SliceRandom;
 
-pub fn answer() -> String {
+pub struct Conch;
+
+#[async_trait]
+impl Command for Conch {
+    type Args = ();
+
+    fn name(&self) -> &'static str {
+        "conch"
+    }
+
+    fn description(&self) -> &'static str {
+        "🐚 - magic conch shell"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["conch Will it rain tomorrow?"]
+    }
+
+    fn parse(&self, _args: &str, _lang: Language) -> Result<Self::Args, String> {
+        Ok(())
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, _args: Self::Args) -> Result<Option<Response>> {
+        Ok(Some(answer(ctx.lang).into()))
+    }
+}
+
+pub fn answer(lang: Language) -> String {
     info!("🐚 answering");
-    let answers = [
-        "Maybe someday.",
-        "Nothing.",
-        "Neither.",
-        "I don't think so.",
-        "No.",
-        "Yes.",
-        "Try asking again.",
-        "You cannot get to the top by sitting on your bottom.",
-        "I see a new sauce in your future.",
-        "Ask next time.",
-        "Follow the seahorse.",
-    ];
+    let answers = tr(lang, "conch.answers").lines().collect::<Vec<_>>();
 
     answers
         .choose(&mut rand::thread_rng())
         .unwrap_or({
             error!("fails to select answer");
-            &"I have no idea."
+            &tr(lang, "no_idea")
         })
         .to_string()
 }
@@ -0,0 +1,67 @@
+use crate::prelude::*;
+use matrix_sdk::ruma::OwnedRoomId;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// More responses than this within [`WINDOW`] in one room look like a loop, e.g. with another bot.
+const MAX_RESPONSES: usize = 20;
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Stops the dispatcher from answering in a room that gets answers at a rate no human would cause.
+#[derive(Default)]
+pub struct LoopGuard {
+    responses: Mutex<HashMap<OwnedRoomId, VecDeque<Instant>>>,
+}
+
+impl LoopGuard {
+    /// Records a response to the room, unless that would exceed the limit.
+    pub fn allow(&self, room_id: &RoomId) -> bool {
+        self.allow_at(room_id, Instant::now())
+    }
+
+    fn allow_at(&self, room_id: &RoomId, now: Instant) -> bool {
+        let mut responses = self.responses.lock().unwrap_or_else(|err| err.into_inner());
+        let sent = responses.entry(room_id.to_owned()).or_default();
+
+        while sent
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= WINDOW)
+        {
+            sent.pop_front();
+        }
+
+        if sent.len() >= MAX_RESPONSES {
+            warn!("🔁 more than {MAX_RESPONSES} responses in room {room_id} within {WINDOW:?}, looks like a loop");
+            return false;
+        }
+
+        sent.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn loop_guard() {
+        let guard = LoopGuard::default();
+        let room_id = RoomId::parse("!WBGmhYXnxVfSYOoHua:matrix.com").expect("room_id");
+        let other_room_id = RoomId::parse("!XCHnhZYoyWgTZPpIvb:matrix.com").expect("room_id");
+        let start = Instant::now();
+
+        for n in 0..MAX_RESPONSES {
+            assert!(guard.allow_at(&room_id, start + Duration::from_secs(n as u64)));
+        }
+        assert!(!guard.allow_at(&room_id, start + Duration::from_secs(30)));
+        assert!(guard.allow_at(&other_room_id, start + Duration::from_secs(30)));
+
+        // the first response left the window
+        assert!(guard.allow_at(&room_id, start + WINDOW));
+        assert!(!guard.allow_at(&room_id, start + WINDOW));
+    }
+}
@@ -1,51 +1,44 @@
-use crate::prelude::*;
-
-pub fn text() -> String {
+use crate::{
+    command::{
+        response::{code, escape},
+        Registry, Response,
+    },
+    locale::{lookup, tr, Language},
+    prelude::*,
+};
+
+pub fn text(registry: &Registry, prefix: &str, disabled: &[String], lang: Language) -> Response {
     info!("ℹ️ helping");
 
-    r#"🤖 Here is a list of my commands:
-
-ℹ️ - help
-!botto
-
-🪙 - coin flip
-!coinflip
-
-👤 - get random user
-!nominate
-
-🎲 - dice roll
-!r d6
-!r 2d8
-!r 2d6 - 1d4 + 3
-
-🔘 - choose an option
-!choose pizza, pasta, sushi
-
-🪨 - rock paper scissors
-!rps rock
-!rps paper
-!rps scissors
-
-⏲️ - reminder [minutes, hours, days]
-!reminder 10 minutes: Check the oven
-!reminder 2 hours: Laundry is done
-!reminder 10 days: Mow the lawn
-
-⏲️🔁 - recurring reminder
-!reminder every 42 days: Get a haircut.
-
-⏲️ 🔀 - random reminder
-!reminder 1-3d: Go to the gym every 1-3 days
-
-⏲️⚙️ - manage reminders
-!reminders - list all reminders
-!deleteReminder 3 - delete 3rd reminder from list
-!deleteAllReminders - delete all reminders
-
-
-🔗 Bot's source code: 
-https://github.com/jflessau/botto
-"#
-    .to_string()
+    let header = tr(lang, "help.header");
+    let mut body = format!("{header}\n");
+    let mut html = format!("{}<br>", escape(header));
+    for command in registry
+        .commands()
+        .filter(|c| !disabled.iter().any(|d| d == c.name()))
+    {
+        // catalogs may translate descriptions and usage examples, one example per line
+        let description = lookup(lang, &format!("{}.description", command.name()))
+            .unwrap_or_else(|| command.description());
+        let usage = lookup(lang, &format!("{}.usage", command.name()))
+            .map(|u| u.lines().collect::<Vec<_>>())
+            .unwrap_or_else(|| command.usage().to_vec());
+
+        body.push_str(&format!("\n{description}\n"));
+        html.push_str(&format!("<br>{}<br>", escape(description)));
+        for usage in usage {
+            body.push_str(&format!("{prefix}{usage}\n"));
+            html.push_str(&format!("{}<br>", code(&format!("{prefix}{usage}"))));
+        }
+    }
+
+    let source = "https://github.com/jflessau/botto";
+    let source_label = tr(lang, "help.source");
+    body.push_str(&format!("\n\n{source_label} \n{source}\n"));
+    html.push_str(&format!(
+        "<br>{} <a href=\"{source}\">{source}</a>",
+        escape(source_label)
+    ));
+
+    Response::new(body, html)
 }
@@ -0,0 +1,280 @@
+use crate::{
+    command::recurrence::{localize, parse_time},
+    locale::{trf, Language},
+    prelude::*,
+    settings::RoomSettings,
+};
+use chrono::NaiveTime;
+use chrono_tz::Tz;
+use rand::Rng;
+use regex::Regex;
+
+/// Daily hours like `9:00-18:00`, overnight like `22:00-7:00` if they end before they start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Hours {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+/// The room's quiet hours on its clock, random reminders aren't sent in them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quiet {
+    pub hours: Option<Hours>,
+    pub tz: Tz,
+}
+
+impl Hours {
+    /// Parses e.g. `9:00-18:00`, `22:00 to 7:00` or `9am and 6pm`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let separator = Regex::new(r"-|–|\band\b|\bto\b").expect("regex is valid");
+        let text = text.trim().to_lowercase();
+        let (start, end) = match separator.splitn(&text, 2).collect::<Vec<_>>()[..] {
+            [start, end] => (start.replace(' ', ""), end.replace(' ', "")),
+            _ => return None,
+        };
+        let start = parse_time(&start).ok()?;
+        let end = parse_time(&end).ok()?;
+
+        (start != end).then_some(Self { start, end })
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        match self.start < self.end {
+            true => self.start <= time && time < self.end,
+            false => self.start <= time || time < self.end,
+        }
+    }
+
+    pub fn describe(&self, lang: Language) -> String {
+        trf(
+            lang,
+            "hours.between",
+            &[
+                ("start", &self.start.format("%H:%M")),
+                ("end", &self.end.format("%H:%M")),
+            ],
+        )
+    }
+}
+
+impl TryFrom<String> for Hours {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value).ok_or_else(|| format!("invalid hours {value}"))
+    }
+}
+
+impl From<Hours> for String {
+    fn from(hours: Hours) -> Self {
+        format!(
+            "{}-{}",
+            hours.start.format("%H:%M"),
+            hours.end.format("%H:%M")
+        )
+    }
+}
+
+impl Quiet {
+    pub fn of(settings: &RoomSettings) -> Self {
+        Self {
+            hours: settings.quiet_hours,
+            tz: settings.timezone.unwrap_or(Tz::UTC),
+        }
+    }
+}
+
+impl Default for Quiet {
+    fn default() -> Self {
+        Self {
+            hours: None,
+            tz: Tz::UTC,
+        }
+    }
+}
+
+/// Longest search for an allowed time after a range without one.
+const SEARCH_DAYS: i64 = 7;
+
+/// A random instant from `min` to `max` inside `hours` and outside the quiet hours, on the room's clock.
+/// Ranges without such an instant, like `1-2h between 9:00 and 18:00` in the evening, get the first one after them.
+pub fn draw(
+    min: DateTime<Utc>,
+    max: DateTime<Utc>,
+    hours: Option<Hours>,
+    quiet: &Quiet,
+) -> DateTime<Utc> {
+    let mut rng = rand::thread_rng();
+    if hours.is_none() && quiet.hours.is_none() {
+        let at = rng.gen_range(min.timestamp()..=max.timestamp());
+        return DateTime::from_timestamp(at, 0).unwrap_or(min);
+    }
+
+    let spans = allowed(min, max, hours, quiet);
+    let total: i64 = spans
+        .iter()
+        .map(|(from, to)| (*to - *from).num_seconds())
+        .sum();
+    if total > 0 {
+        let mut offset = rng.gen_range(0..total);
+        for (from, to) in spans {
+            let len = (to - from).num_seconds();
+            if offset < len {
+                return from + chrono::Duration::seconds(offset);
+            }
+            offset -= len;
+        }
+    }
+
+    let after = max + chrono::Duration::days(SEARCH_DAYS);
+    match allowed(max, after, hours, quiet).first() {
+        Some((from, _)) => *from,
+        // e.g. hours that are all quiet, which is better sent than never
+        None => min,
+    }
+}
+
+/// The spans from `from` to `to` inside `hours` and outside the quiet hours, in order.
+fn allowed(
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    hours: Option<Hours>,
+    quiet: &Quiet,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let is_allowed = |time: NaiveTime| {
+        hours.is_none_or(|h| h.contains(time)) && !quiet.hours.is_some_and(|q| q.contains(time))
+    };
+    // whether a time is allowed only changes at these times of the day
+    let mut changes: Vec<NaiveTime> = [hours, quiet.hours]
+        .into_iter()
+        .flatten()
+        .flat_map(|h| [h.start, h.end])
+        .chain([NaiveTime::MIN])
+        .collect();
+    changes.sort();
+    changes.dedup();
+
+    let mut spans: Vec<(DateTime<Utc>, DateTime<Utc>)> = Vec::new();
+    let mut day = from.with_timezone(&quiet.tz).date_naive();
+    let last = to.with_timezone(&quiet.tz).date_naive();
+    while day <= last {
+        let Some(next_day) = day.succ_opt() else {
+            break;
+        };
+        for (i, start) in changes.iter().enumerate() {
+            if !is_allowed(*start) {
+                continue;
+            }
+            let end = match changes.get(i + 1) {
+                Some(end) => day.and_time(*end),
+                None => next_day.and_time(NaiveTime::MIN),
+            };
+            let span_from = localize(quiet.tz, day.and_time(*start)).max(from);
+            let span_to = localize(quiet.tz, end).min(to);
+            if span_from >= span_to {
+                continue;
+            }
+            // e.g. allowed until midnight and after it
+            match spans.last_mut() {
+                Some((_, last_to)) if *last_to == span_from => *last_to = span_to,
+                _ => spans.push((span_from, span_to)),
+            }
+        }
+        day = next_day;
+    }
+
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        text.parse().expect("valid datetime")
+    }
+
+    fn hours(text: &str) -> Hours {
+        Hours::parse(text).expect("valid hours")
+    }
+
+    #[test]
+    pub fn parse_hours() {
+        assert_eq!(String::from(hours("9:00-18:00")), "09:00-18:00");
+        assert_eq!(String::from(hours("22 to 7")), "22:00-07:00");
+        assert_eq!(String::from(hours("9 am and 6 pm")), "09:00-18:00");
+        for text in ["", "9:00", "9:00-9:00", "9:00-25:00", "later"] {
+            assert_eq!(Hours::parse(text), None, "{text}");
+        }
+
+        let night = hours("22:00-7:00");
+        let time = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+        assert!(night.contains(time(23)) && night.contains(time(3)));
+        assert!(!night.contains(time(7)) && !night.contains(time(12)));
+    }
+
+    #[test]
+    pub fn allowed_spans() {
+        let berlin = Quiet {
+            hours: Some(hours("22:00-7:00")),
+            tz: Tz::Europe__Berlin,
+        };
+        // Berlin is 2 hours ahead in October
+        assert_eq!(
+            allowed(
+                at("2026-10-18T00:00:00Z"),
+                at("2026-10-19T12:00:00Z"),
+                Some(hours("9:00-18:00")),
+                &berlin
+            ),
+            [
+                (at("2026-10-18T07:00:00Z"), at("2026-10-18T16:00:00Z")),
+                (at("2026-10-19T07:00:00Z"), at("2026-10-19T12:00:00Z")),
+            ]
+        );
+        assert_eq!(
+            allowed(
+                at("2026-10-18T12:00:00Z"),
+                at("2026-10-19T12:00:00Z"),
+                None,
+                &berlin
+            ),
+            [
+                (at("2026-10-18T12:00:00Z"), at("2026-10-18T20:00:00Z")),
+                (at("2026-10-19T05:00:00Z"), at("2026-10-19T12:00:00Z")),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn draw_in_hours() {
+        let quiet = Quiet {
+            hours: Some(hours("22:00-7:00")),
+            tz: Tz::UTC,
+        };
+        let window = hours("9:00-18:00");
+        for _ in 0..100 {
+            let drawn = draw(
+                at("2026-10-18T12:00:00Z"),
+                at("2026-10-21T12:00:00Z"),
+                Some(window),
+                &quiet,
+            );
+            let time = drawn.time();
+            assert!(window.contains(time), "{drawn}");
+            assert!(drawn >= at("2026-10-18T12:00:00Z") && drawn < at("2026-10-21T12:00:00Z"));
+        }
+
+        // the evening has no allowed time, so it's the next morning
+        assert_eq!(
+            draw(
+                at("2026-10-18T19:00:00Z"),
+                at("2026-10-18T20:00:00Z"),
+                Some(window),
+                &quiet
+            ),
+            at("2026-10-19T09:00:00Z")
+        );
+    }
+}
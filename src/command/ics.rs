@@ -0,0 +1,376 @@
+use crate::{command::span::Span, prelude::*};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono_tz::Tz;
+
+/// Lines of iCalendar files are folded after this many bytes.
+const LINE_LEN: usize = 75;
+
+/// A VEVENT of an iCalendar file, with only the properties reminders have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub start: Start,
+    /// The RRULE without its name, e.g. `FREQ=WEEKLY;BYDAY=MO`.
+    pub rrule: Option<String>,
+}
+
+/// A content line like `DTSTART;TZID=Europe/Berlin:20261018T140000`.
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+/// The DTSTART of an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Start {
+    /// `DTSTART:20261018T120000Z`
+    Utc(DateTime<Utc>),
+    /// `DTSTART;TZID=Europe/Berlin:20261018T140000`, floating times and unknown timezones have none.
+    Local(NaiveDateTime, Option<Tz>),
+    /// `DTSTART;VALUE=DATE:20261018`
+    Date(NaiveDate),
+}
+
+impl Start {
+    pub fn tz(&self) -> Option<Tz> {
+        match self {
+            Self::Local(_, tz) => *tz,
+            Self::Utc(_) | Self::Date(_) => None,
+        }
+    }
+
+    /// The instant it starts at, floating times and dates are on the clock of `tz`, dates at `default_time`.
+    pub fn instant(&self, tz: Tz, default_time: NaiveTime) -> DateTime<Utc> {
+        use crate::command::recurrence::localize;
+
+        match self {
+            Self::Utc(at) => *at,
+            Self::Local(local, zone) => localize(zone.unwrap_or(tz), *local),
+            Self::Date(date) => localize(tz, date.and_time(default_time)),
+        }
+    }
+
+    /// Parses the value of e.g. `DTSTART` or `UNTIL` with the property's parameters.
+    pub fn parse(params: &[(String, String)], value: &str) -> Result<Self> {
+        let param = |name: &str| params.iter().find(|(k, _)| k == name).map(|(_, v)| v);
+        if value.len() == 8 || param("VALUE").is_some_and(|v| v == "DATE") {
+            let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+                .with_context(|| format!("invalid date {value}"))?;
+            return Ok(Self::Date(date));
+        }
+
+        let (local, utc) = match value.strip_suffix('Z') {
+            Some(local) => (local, true),
+            None => (value, false),
+        };
+        let local = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S")
+            .with_context(|| format!("invalid date and time {value}"))?;
+
+        Ok(match utc {
+            true => Self::Utc(local.and_utc()),
+            false => Self::Local(local, param("TZID").and_then(|tz| tz.parse().ok())),
+        })
+    }
+}
+
+impl Display for Start {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self {
+            Self::Utc(at) => write!(f, ":{}", at.format("%Y%m%dT%H%M%SZ")),
+            Self::Local(local, Some(tz)) => {
+                write!(f, ";TZID={}:{}", tz.name(), local.format("%Y%m%dT%H%M%S"))
+            }
+            Self::Local(local, None) => write!(f, ":{}", local.format("%Y%m%dT%H%M%S")),
+            Self::Date(date) => write!(f, ";VALUE=DATE:{}", date.format("%Y%m%d")),
+        }
+    }
+}
+
+/// An iCalendar file of the events, stamped with `now`.
+pub fn write(events: &[Event], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//botto//reminders//EN".to_string(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%SZ")));
+        lines.push(format!("DTSTART{}", event.start));
+        if let Some(rrule) = &event.rrule {
+            lines.push(format!("RRULE:{rrule}"));
+        }
+        lines.push(format!("SUMMARY:{}", escape(&event.summary)));
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape(description)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+/// The events of an iCalendar file, each one an error if it lacks what reminders need.
+pub fn parse(text: &str) -> Result<Vec<Result<Event>>> {
+    // long lines continue on the next one after a space or tab
+    let text = text
+        .replace("\r\n ", "")
+        .replace("\r\n\t", "")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+    let mut lines = text.lines().filter(|l| !l.is_empty());
+    if lines.next().map(str::trim) != Some("BEGIN:VCALENDAR") {
+        bail!("not an iCalendar file");
+    }
+
+    let mut events = Vec::new();
+    let mut event: Option<Vec<Property>> = None;
+    // components inside events like alarms are skipped
+    let mut nested = 0;
+    for line in lines {
+        let Some(property) = Property::parse(line) else {
+            continue;
+        };
+        match (property.name.as_str(), property.value.as_str(), &mut event) {
+            ("BEGIN", "VEVENT", None) => event = Some(Vec::new()),
+            ("END", "VEVENT", Some(_)) if nested == 0 => {
+                if let Some(properties) = event.take() {
+                    events.push(Event::from_properties(&properties));
+                }
+            }
+            ("BEGIN", _, Some(_)) => nested += 1,
+            ("END", _, Some(_)) => nested -= 1,
+            (_, _, Some(properties)) if nested == 0 => properties.push(property),
+            _ => {}
+        }
+    }
+
+    Ok(events)
+}
+
+impl Event {
+    fn from_properties(properties: &[Property]) -> Result<Self> {
+        let find = |name: &str| properties.iter().find(|p| p.name == name);
+
+        let start = find("DTSTART").context("event has no DTSTART")?;
+        let summary = find("SUMMARY")
+            .map(|p| unescape(&p.value))
+            .context("event has no SUMMARY")?;
+
+        Ok(Self {
+            uid: find("UID").map(|p| p.value.clone()).unwrap_or_default(),
+            summary,
+            description: find("DESCRIPTION").map(|p| unescape(&p.value)),
+            start: Start::parse(&start.params, &start.value)?,
+            rrule: find("RRULE").map(|p| p.value.clone()),
+        })
+    }
+}
+
+impl Property {
+    /// Splits a line into its name, parameters and value.
+    fn parse(line: &str) -> Option<Self> {
+        // colons in quoted parameters don't end the name
+        let mut quoted = false;
+        let colon = line.char_indices().find_map(|(i, c)| {
+            match c {
+                '"' => quoted = !quoted,
+                ':' if !quoted => return Some(i),
+                _ => {}
+            }
+            None
+        })?;
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+        let mut parts = head.split(';');
+        let name = parts.next()?.to_uppercase();
+        let params = parts
+            .filter_map(|p| p.split_once('='))
+            .map(|(k, v)| (k.to_uppercase(), v.trim_matches('"').to_string()))
+            .collect();
+
+        Some(Self {
+            name,
+            params,
+            value: value.to_string(),
+        })
+    }
+}
+
+/// The RRULE of reminders with a fixed interval like `every 2 weeks`, `None` for ones like `every 1 month 2 days`.
+pub fn span_rule(span: Span) -> Option<String> {
+    const UNITS: [(&str, u64); 5] = [
+        ("WEEKLY", 7 * 24 * 60 * 60),
+        ("DAILY", 24 * 60 * 60),
+        ("HOURLY", 60 * 60),
+        ("MINUTELY", 60),
+        ("SECONDLY", 1),
+    ];
+
+    match (span.months, span.seconds) {
+        (0, 0) => None,
+        (months, 0) if months % 12 == 0 => Some(format!("FREQ=YEARLY;INTERVAL={}", months / 12)),
+        (months, 0) => Some(format!("FREQ=MONTHLY;INTERVAL={months}")),
+        (0, seconds) => UNITS
+            .iter()
+            .find(|(_, unit)| seconds % unit == 0)
+            .map(|(freq, unit)| format!("FREQ={freq};INTERVAL={}", seconds / unit)),
+        _ => None,
+    }
+}
+
+/// The interval of an RRULE's `FREQ` and `INTERVAL`, the inverse of [`span_rule`].
+pub fn rule_span(freq: &str, interval: u32) -> Option<Span> {
+    let seconds = |unit: u64| Span {
+        months: 0,
+        seconds: unit * u64::from(interval),
+    };
+
+    match freq {
+        "YEARLY" => Some(Span {
+            months: interval.checked_mul(12)?,
+            seconds: 0,
+        }),
+        "MONTHLY" => Some(Span {
+            months: interval,
+            seconds: 0,
+        }),
+        "WEEKLY" => Some(seconds(7 * 24 * 60 * 60)),
+        "DAILY" => Some(seconds(24 * 60 * 60)),
+        "HOURLY" => Some(seconds(60 * 60)),
+        "MINUTELY" => Some(seconds(60)),
+        "SECONDLY" => Some(seconds(1)),
+        _ => None,
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => res.push('\n'),
+                Some(c) => res.push(c),
+                None => {}
+            },
+            (c, false) => res.push(c),
+        }
+    }
+
+    res
+}
+
+/// The line with CRLF, longer ones are folded without splitting characters.
+fn fold(line: &str) -> String {
+    let mut res = String::with_capacity(line.len() + 2);
+    let mut len = 0;
+    for c in line.chars() {
+        if len + c.len_utf8() > LINE_LEN {
+            res.push_str("\r\n ");
+            len = 1;
+        }
+        res.push(c);
+        len += c.len_utf8();
+    }
+    res.push_str("\r\n");
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn write_and_parse() {
+        let now = "2026-10-18T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let local = NaiveDate::from_ymd_opt(2026, 10, 19)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let events = vec![
+            Event {
+                uid: "a@botto".to_string(),
+                summary: "Standup; daily, with everyone".to_string(),
+                description: None,
+                start: Start::Local(local, Some(Tz::Europe__Berlin)),
+                rrule: Some("FREQ=WEEKLY;BYDAY=MO,WE;BYHOUR=9;BYMINUTE=0".to_string()),
+            },
+            Event {
+                uid: "b@botto".to_string(),
+                summary: "Go to the gym ".repeat(8),
+                description: Some("every 1 - 3 days".to_string()),
+                start: Start::Utc(now),
+                rrule: None,
+            },
+        ];
+
+        let text = write(&events, now);
+        assert!(text.contains("DTSTART;TZID=Europe/Berlin:20261019T090000\r\n"));
+        assert!(text.contains("SUMMARY:Standup\\; daily\\, with everyone\r\n"));
+        assert!(text.lines().all(|line| line.len() <= LINE_LEN));
+
+        let parsed: Vec<_> = parse(&text)
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(parsed, events);
+    }
+
+    #[test]
+    pub fn parse_calendar() {
+        let text = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nDTSTART;VALUE=DATE:20261224\nSUMMARY:Gifts\nBEGIN:VALARM\nSUMMARY:Alarm\nEND:VALARM\nEND:VEVENT\nBEGIN:VEVENT\nDTSTART;TZID=\"W. Europe Standard Time\":20261019T090000\nSUMMARY:Stand\n up\nEND:VEVENT\nBEGIN:VEVENT\nSUMMARY:No start\nEND:VEVENT\nEND:VCALENDAR\n";
+        let events = parse(text).unwrap();
+
+        let gifts = events[0].as_ref().unwrap();
+        assert_eq!(gifts.summary, "Gifts");
+        assert_eq!(
+            gifts.start,
+            Start::Date(NaiveDate::from_ymd_opt(2026, 12, 24).unwrap())
+        );
+        // unknown timezones are floating
+        let standup = events[1].as_ref().unwrap();
+        assert_eq!(standup.summary, "Standup");
+        assert_eq!(standup.start.tz(), None);
+        assert!(events[2].is_err());
+
+        assert!(parse("not a calendar").is_err());
+    }
+
+    #[test]
+    pub fn span_rules() {
+        let span = |months, seconds| Span { months, seconds };
+        let cases = [
+            (span(0, 2 * 60 * 60), Some("FREQ=HOURLY;INTERVAL=2")),
+            (span(0, 17 * 24 * 60 * 60), Some("FREQ=DAILY;INTERVAL=17")),
+            (span(0, 14 * 24 * 60 * 60), Some("FREQ=WEEKLY;INTERVAL=2")),
+            (span(1, 0), Some("FREQ=MONTHLY;INTERVAL=1")),
+            (span(24, 0), Some("FREQ=YEARLY;INTERVAL=2")),
+            (span(1, 2 * 24 * 60 * 60), None),
+        ];
+        for (span, rule) in cases {
+            assert_eq!(span_rule(span).as_deref(), rule, "{span:?}");
+            if let Some(rule) = rule {
+                let (freq, interval) = rule
+                    .trim_start_matches("FREQ=")
+                    .split_once(";INTERVAL=")
+                    .unwrap();
+                assert_eq!(rule_span(freq, interval.parse().unwrap()), Some(span));
+            }
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use crate::prelude::*;
+use matrix_sdk::ruma::{OwnedRoomId, OwnedUserId, UserId};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Buckets untouched for this long are full again and can be forgotten.
+const IDLE: Duration = Duration::from_secs(60 * 60);
+
+/// `burst` uses within `period`, refilled continuously.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rate {
+    pub burst: u32,
+    pub period: Duration,
+}
+
+impl Rate {
+    pub const fn new(burst: u32, period_secs: u64) -> Self {
+        Self {
+            burst,
+            period: Duration::from_secs(period_secs),
+        }
+    }
+
+    fn per_sec(&self) -> f64 {
+        self.burst as f64 / self.period.as_secs_f64()
+    }
+}
+
+/// Parses `5/30`, meaning 5 uses per 30 seconds.
+impl FromStr for Rate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let Some((burst, period)) = s.trim().split_once('/') else {
+            bail!("rate {s} is not in the format <uses>/<seconds>");
+        };
+        let burst: u32 = burst
+            .trim()
+            .parse()
+            .context("fails to parse uses of rate")?;
+        let period: u64 = period
+            .trim()
+            .parse()
+            .context("fails to parse seconds of rate")?;
+        if burst == 0 || period == 0 {
+            bail!("uses and seconds of rate {s} must be greater than 0");
+        }
+
+        Ok(Self::new(burst, period))
+    }
+}
+
+/// How often a command may be used by one sender and within one room.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub user: Rate,
+    pub room: Rate,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            user: Rate::new(5, 30),
+            room: Rate::new(15, 30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Scope {
+    User(OwnedUserId),
+    Room(OwnedRoomId),
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    /// Whether the sender or room was told about being throttled since the last allowed use.
+    notified: bool,
+}
+
+impl Bucket {
+    fn refill(&mut self, rate: &Rate, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate.per_sec()).min(rate.burst as f64);
+        self.updated = now;
+    }
+
+    fn wait(&self, rate: &Rate) -> Duration {
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / rate.per_sec())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Allow,
+    /// `notify` is only set the first time a sender or room hits the limit, so throttling stays quiet.
+    Throttle {
+        wait: Duration,
+        notify: bool,
+    },
+}
+
+/// Token buckets per command, for each sender and each room.
+#[derive(Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<(&'static str, Scope), Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn check(
+        &self,
+        command: &'static str,
+        room_id: &RoomId,
+        sender: &UserId,
+        limit: &RateLimit,
+    ) -> Verdict {
+        self.check_at(command, room_id, sender, limit, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        command: &'static str,
+        room_id: &RoomId,
+        sender: &UserId,
+        limit: &RateLimit,
+        now: Instant,
+    ) -> Verdict {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|err| err.into_inner());
+        buckets.retain(|_, b| now.saturating_duration_since(b.updated) < IDLE);
+
+        let scopes = [
+            (Scope::User(sender.to_owned()), limit.user),
+            (Scope::Room(room_id.to_owned()), limit.room),
+        ];
+
+        let mut wait = Duration::ZERO;
+        let mut notify = false;
+        for (scope, rate) in &scopes {
+            let bucket = buckets
+                .entry((command, scope.clone()))
+                .or_insert_with(|| Bucket {
+                    tokens: rate.burst as f64,
+                    updated: now,
+                    notified: false,
+                });
+            bucket.refill(rate, now);
+
+            let bucket_wait = bucket.wait(rate);
+            if !bucket_wait.is_zero() {
+                wait = wait.max(bucket_wait);
+                notify |= !bucket.notified;
+                bucket.notified = true;
+            }
+        }
+
+        if !wait.is_zero() {
+            debug!("⏳ throttling {command} of {sender} in room {room_id} for {wait:?}");
+            return Verdict::Throttle { wait, notify };
+        }
+
+        for (scope, _) in scopes {
+            if let Some(bucket) = buckets.get_mut(&(command, scope)) {
+                bucket.tokens -= 1.0;
+                bucket.notified = false;
+            }
+        }
+
+        Verdict::Allow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn rate_from_str() {
+        assert_eq!("5/30".parse::<Rate>().ok(), Some(Rate::new(5, 30)));
+        assert_eq!(" 1 / 60 ".parse::<Rate>().ok(), Some(Rate::new(1, 60)));
+        assert!("5".parse::<Rate>().is_err());
+        assert!("0/30".parse::<Rate>().is_err());
+        assert!("5/0".parse::<Rate>().is_err());
+        assert!("a/b".parse::<Rate>().is_err());
+    }
+
+    #[test]
+    pub fn rate_limiter() {
+        let limiter = RateLimiter::default();
+        let room_id = RoomId::parse("!WBGmhYXnxVfSYOoHua:matrix.com").expect("room_id");
+        let alice = UserId::parse("@alice:matrix.com").expect("user_id");
+        let bob = UserId::parse("@bob:matrix.com").expect("user_id");
+        let limit = RateLimit {
+            user: Rate::new(2, 10),
+            room: Rate::new(3, 10),
+        };
+        let start = Instant::now();
+
+        let check = |user: &UserId, secs: u64| {
+            limiter.check_at(
+                "roll",
+                &room_id,
+                user,
+                &limit,
+                start + Duration::from_secs(secs),
+            )
+        };
+
+        assert_eq!(check(&alice, 0), Verdict::Allow);
+        assert_eq!(check(&alice, 0), Verdict::Allow);
+        assert_eq!(
+            check(&alice, 0),
+            Verdict::Throttle {
+                wait: Duration::from_secs(5),
+                notify: true
+            }
+        );
+        // only the first throttled use is announced
+        assert!(matches!(
+            check(&alice, 1),
+            Verdict::Throttle { notify: false, .. }
+        ));
+
+        // the room still has a token left, then it is exhausted for everyone
+        assert_eq!(check(&bob, 1), Verdict::Allow);
+        assert!(matches!(
+            check(&bob, 1),
+            Verdict::Throttle { notify: true, .. }
+        ));
+
+        // one token per 5 seconds for alice
+        assert_eq!(check(&alice, 5), Verdict::Allow);
+        assert!(matches!(
+            check(&alice, 6),
+            Verdict::Throttle { notify: true, .. }
+        ));
+
+        // other commands have their own buckets
+        assert_eq!(
+            limiter.check_at("coinflip", &room_id, &alice, &limit, start),
+            Verdict::Allow
+        );
+    }
+}
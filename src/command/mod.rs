@@ -1,8 +1,432 @@
+use crate::{
+    config::Config,
+    locale::{tr, trf, trf_html, Language},
+    prelude::*,
+    settings::RoomSettings,
+};
+pub use async_trait::async_trait;
+use guard::LoopGuard;
+use limit::{Rate, RateLimit, RateLimiter, Verdict};
+use matrix_sdk::ruma::{EventId, UserId};
+use matrix_sdk::Room;
+pub use response::Response;
+use response::{code, escape};
+use std::collections::HashMap;
+
+pub mod botto;
+pub mod catch_up;
 pub mod choose;
 pub mod coin;
 pub mod conch;
+pub mod guard;
 pub mod help;
+pub mod hours;
+pub mod ics;
+pub mod limit;
 pub mod nominate;
+pub mod recurrence;
 pub mod reminder;
+pub mod response;
 pub mod roll;
 pub mod rps;
+pub mod span;
+pub mod timezone;
+
+/// Everything a command needs to know about the message it answers.
+pub struct Ctx<'a> {
+    pub room: &'a Room,
+    pub db: &'a Surreal<Any>,
+    pub registry: &'a Registry,
+    pub config: &'a Config,
+    pub settings: &'a RoomSettings,
+    pub sender: &'a UserId,
+    /// Whether the message is an edit of an earlier command.
+    pub edit: bool,
+    /// The message it replies to, e.g. an uploaded file for `!reminders import`.
+    pub reply_to: Option<&'a EventId>,
+    /// The prefix in effect for this room.
+    pub prefix: &'a str,
+    /// The language in effect for this room.
+    pub lang: Language,
+}
+
+/// A chat command, invoked with the room's prefix and its name or an alias, followed by its arguments.
+#[async_trait]
+pub trait Command: Send + Sync {
+    /// Arguments parsed from the text after the command name.
+    type Args: Send;
+
+    fn name(&self) -> &'static str;
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Whether an edit of the command message runs the command again and edits the response.
+    /// Commands that change state, like creating a reminder, would repeat the change.
+    fn reruns_on_edit(&self) -> bool {
+        true
+    }
+
+    /// Overrides the default rate limit, unless the deployment configures one for this command.
+    fn rate_limit(&self) -> Option<RateLimit> {
+        None
+    }
+
+    /// Emoji and a few words, shown as the heading in the help text.
+    /// Catalogs may translate it with the key `<name>.description`.
+    fn description(&self) -> &'static str;
+
+    /// Example invocations without the prefix, shown in the help text.
+    /// Catalogs may translate them with the key `<name>.usage`, one example per line.
+    fn usage(&self) -> &'static [&'static str];
+
+    /// Parses the text after the command name. The error is sent to the room as is.
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String>;
+
+    async fn run(&self, ctx: &Ctx<'_>, args: Self::Args) -> Result<Option<Response>>;
+}
+
+/// Object safe counterpart of [`Command`], so commands with different argument types fit in one registry.
+#[async_trait]
+pub trait Handler: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn aliases(&self) -> &'static [&'static str];
+    fn reruns_on_edit(&self) -> bool;
+    fn rate_limit(&self) -> Option<RateLimit>;
+    fn description(&self) -> &'static str;
+    fn usage(&self) -> &'static [&'static str];
+    async fn handle(&self, ctx: &Ctx<'_>, args: &str) -> Result<Option<Response>>;
+}
+
+#[async_trait]
+impl<C: Command> Handler for C {
+    fn name(&self) -> &'static str {
+        Command::name(self)
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        Command::aliases(self)
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        Command::reruns_on_edit(self)
+    }
+
+    fn rate_limit(&self) -> Option<RateLimit> {
+        Command::rate_limit(self)
+    }
+
+    fn description(&self) -> &'static str {
+        Command::description(self)
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        Command::usage(self)
+    }
+
+    async fn handle(&self, ctx: &Ctx<'_>, args: &str) -> Result<Option<Response>> {
+        match self.parse(args, ctx.lang) {
+            Ok(args) => self.run(ctx, args).await,
+            Err(msg) => Ok(Some(Response::plain(msg))),
+        }
+    }
+}
+
+pub struct Registry {
+    commands: Vec<Box<dyn Handler>>,
+    loop_guard: LoopGuard,
+    rate_limiter: RateLimiter,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        let mut registry = Self {
+            commands: vec![],
+            loop_guard: LoopGuard::default(),
+            rate_limiter: RateLimiter::default(),
+        };
+
+        registry.register(botto::Botto);
+        registry.register(coin::Coinflip);
+        registry.register(conch::Conch);
+        registry.register(nominate::Nominate);
+        registry.register(roll::Roll);
+        registry.register(choose::Choose);
+        registry.register(rps::RockPaperScissors);
+        reminder::register(&mut registry);
+        registry.register(timezone::Timezone);
+
+        registry
+    }
+
+    pub fn register(&mut self, command: impl Command + 'static) {
+        let name = Command::name(&command);
+        if self.find(name).is_some() {
+            warn!("command {name} is registered twice, ignoring the second one");
+            return;
+        }
+        self.commands.push(Box::new(command));
+    }
+
+    pub fn commands(&self) -> impl Iterator<Item = &dyn Handler> {
+        self.commands.iter().map(|c| c.as_ref())
+    }
+
+    /// Finds a command by its name or one of its aliases, ignoring case.
+    pub fn find(&self, name: &str) -> Option<&dyn Handler> {
+        let name = name.to_lowercase();
+        self.commands()
+            .find(|c| c.name() == name || c.aliases().contains(&name.as_str()))
+    }
+
+    /// Names and aliases similar to the given name, closest first.
+    pub fn suggest(&self, name: &str) -> Vec<&'static str> {
+        let name = name.to_lowercase();
+        let mut candidates = self
+            .commands()
+            .flat_map(|c| std::iter::once(c.name()).chain(c.aliases().iter().copied()))
+            .filter_map(|candidate| {
+                let distance = strsim::damerau_levenshtein(&name, candidate);
+                // typos grow with the word, abbreviations like `coin` for `coinflip` are close too
+                let len = candidate.chars().count();
+                let close = (distance <= (len / 4).max(1) && distance < len)
+                    || (name.chars().count() >= 3 && candidate.starts_with(&name));
+                close.then_some((distance, candidate))
+            })
+            .collect::<Vec<_>>();
+        candidates.sort();
+
+        candidates
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .take(MAX_SUGGESTIONS)
+            .collect()
+    }
+
+    fn did_you_mean(&self, ctx: &Ctx<'_>, name: &str) -> Option<Response> {
+        let mut suggestions = self.suggest(name);
+        suggestions.retain(|s| {
+            self.find(s)
+                .is_some_and(|c| ctx.settings.is_enabled(c.name()))
+        });
+        if suggestions.is_empty() {
+            return None;
+        }
+
+        // suggestions answer typos, not a conversation, so they are rare per user and room
+        let limit = RateLimit {
+            user: Rate::new(1, 60),
+            room: Rate::new(3, 5 * 60),
+        };
+        let verdict =
+            self.rate_limiter
+                .check("did-you-mean", ctx.room.room_id(), ctx.sender, &limit);
+        if verdict != Verdict::Allow {
+            return None;
+        }
+
+        info!("🤔 suggesting {suggestions:?} for {name}");
+
+        let prefix = ctx.prefix;
+        let or = tr(ctx.lang, "did_you_mean.or");
+        let body = trf(
+            ctx.lang,
+            "did_you_mean",
+            &[
+                ("command", &format!("{prefix}{name}")),
+                (
+                    "suggestions",
+                    &suggestions
+                        .iter()
+                        .map(|s| format!("{prefix}{s}"))
+                        .collect::<Vec<_>>()
+                        .join(or),
+                ),
+                ("help", &format!("{prefix}botto")),
+            ],
+        );
+        let html = trf_html(
+            ctx.lang,
+            "did_you_mean",
+            &[
+                ("command", &code(&format!("{prefix}{name}"))),
+                (
+                    "suggestions",
+                    &suggestions
+                        .iter()
+                        .map(|s| code(&format!("{prefix}{s}")))
+                        .collect::<Vec<_>>()
+                        .join(&escape(or)),
+                ),
+                ("help", &code(&format!("{prefix}botto"))),
+            ],
+        );
+
+        Some(Response::new(body, html))
+    }
+
+    /// Splits a message into command and arguments and runs the matching command, if any.
+    pub async fn dispatch(&self, ctx: &Ctx<'_>, text: &str) -> Result<Option<Response>> {
+        let Some((name, args)) = split(text, ctx.prefix) else {
+            return Ok(None);
+        };
+
+        let Some(command) = self.find(name) else {
+            trace!("no command matches {name}");
+            return Ok(self.did_you_mean(ctx, name));
+        };
+
+        if ctx.edit && !command.reruns_on_edit() {
+            debug!("ignoring edit of command {}", command.name());
+            return Ok(None);
+        }
+
+        if let Admission::Refused(resp) = self.admit(ctx, command.name()) {
+            return Ok(resp);
+        }
+
+        debug!("dispatching to command {}", command.name());
+        command.handle(ctx, args).await
+    }
+
+    /// Whether the command `name` may answer now: it's enabled in the room, within its rate limits and no loop is suspected.
+    /// Snoozes, which aren't dispatched, are checked as `reminder`.
+    pub fn admit(&self, ctx: &Ctx<'_>, name: &str) -> Admission {
+        let Some(command) = self.find(name) else {
+            return Admission::Refused(None);
+        };
+
+        if !ctx.settings.is_enabled(command.name()) {
+            debug!(
+                "command {} is disabled in room {}",
+                command.name(),
+                ctx.room.room_id()
+            );
+            return Admission::Refused(None);
+        }
+
+        let limit = rate_limit(command, ctx.config);
+        let verdict =
+            self.rate_limiter
+                .check(command.name(), ctx.room.room_id(), ctx.sender, &limit);
+        if let Verdict::Throttle { wait, notify } = verdict {
+            let command = format!("{}{}", ctx.prefix, command.name());
+            let seconds = wait.as_secs_f64().ceil();
+            return Admission::Refused(notify.then(|| {
+                Response::new(
+                    trf(
+                        ctx.lang,
+                        "throttled",
+                        &[("command", &command), ("seconds", &seconds)],
+                    ),
+                    trf_html(
+                        ctx.lang,
+                        "throttled",
+                        &[("command", &code(&command)), ("seconds", &seconds)],
+                    ),
+                )
+            }));
+        }
+
+        if !self.loop_guard.allow(ctx.room.room_id()) {
+            return Admission::Refused(None);
+        }
+
+        Admission::Run
+    }
+}
+
+/// Whether a command may answer, see [`Registry::admit`].
+pub enum Admission {
+    Run,
+    /// With the answer instead, if any, e.g. a note that the sender is throttled.
+    Refused(Option<Response>),
+}
+
+/// The deployment's limit for the command wins over the command's own, which wins over the deployment's default.
+fn rate_limit(command: &dyn Handler, config: &Config) -> RateLimit {
+    let own = command.rate_limit();
+    let rate = |configured: &HashMap<String, Rate>, own: Option<Rate>, default: Rate| {
+        configured
+            .get(command.name())
+            .copied()
+            .or(own)
+            .or_else(|| configured.get("*").copied())
+            .unwrap_or(default)
+    };
+
+    let default = RateLimit::default();
+    RateLimit {
+        user: rate(&config.user_rate_limits, own.map(|l| l.user), default.user),
+        room: rate(&config.room_rate_limits, own.map(|l| l.room), default.room),
+    }
+}
+
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Splits `<prefix>name args` into `name` and the trimmed `args`.
+fn split<'a>(text: &'a str, prefix: &str) -> Option<(&'a str, &'a str)> {
+    let rest = text.trim().strip_prefix(prefix)?;
+    let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    if name.is_empty() {
+        return None;
+    }
+
+    Some((name, args.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn split_command() {
+        assert_eq!(split("!r 1d20", "!"), Some(("r", "1d20")));
+        assert_eq!(split("  !reminders  ", "!"), Some(("reminders", "")));
+        assert_eq!(
+            split("!reminder every 2h: Drink water", "!"),
+            Some(("reminder", "every 2h: Drink water"))
+        );
+        assert_eq!(split("!", "!"), None);
+        assert_eq!(split("! r", "!"), None);
+        assert_eq!(split("r 1d20", "!"), None);
+        assert_eq!(split("?r 1d20", "!"), None);
+        assert_eq!(split("?r 1d20", "?"), Some(("r", "1d20")));
+        assert_eq!(split("bot: coinflip", "bot:"), None);
+        assert_eq!(split("bot:coinflip", "bot:"), Some(("coinflip", "")));
+    }
+
+    #[test]
+    pub fn find_command() {
+        let registry = Registry::new();
+        assert_eq!(registry.find("r").map(|c| c.name()), Some("roll"));
+        assert_eq!(registry.find("Roll").map(|c| c.name()), Some("roll"));
+        assert_eq!(
+            registry.find("reminder").map(|c| c.name()),
+            Some("reminder")
+        );
+        assert_eq!(
+            registry.find("reminders").map(|c| c.name()),
+            Some("reminders")
+        );
+        assert_eq!(
+            registry.find("deleteAllReminders").map(|c| c.name()),
+            Some("deleteallreminders")
+        );
+        assert!(registry.find("remidner").is_none());
+    }
+
+    #[test]
+    pub fn suggest_command() {
+        let registry = Registry::new();
+        assert_eq!(registry.suggest("remidner")[0], "reminder");
+        assert_eq!(registry.suggest("coin"), vec!["coinflip"]);
+        assert_eq!(registry.suggest("Nominat"), vec!["nominate"]);
+        assert_eq!(registry.suggest("deletereminders")[0], "deletereminder");
+        assert!(registry.suggest("important").is_empty());
+        assert_eq!(registry.suggest("rsp"), vec!["rps"]);
+        assert!(registry.suggest("x").is_empty());
+        assert!(registry.suggest("hello").is_empty());
+    }
+}
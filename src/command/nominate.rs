@@ -1,9 +1,52 @@
-use crate::prelude::*;
+use crate::{
+    command::{
+        async_trait,
+        limit::{Rate, RateLimit},
+        Command, Ctx, Response,
+    },
+    locale::{tr, Language},
+    prelude::*,
+};
 use matrix_sdk::{room::Room, RoomMemberships};
 use rand::seq::SliceRandom;
 use std::env;
 
-pub async fn user(room: &Room) -> String {
+pub struct Nominate;
+
+#[async_trait]
+impl Command for Nominate {
+    type Args = ();
+
+    fn name(&self) -> &'static str {
+        "nominate"
+    }
+
+    fn rate_limit(&self) -> Option<RateLimit> {
+        // pinging people is noisier than rolling dice
+        Some(RateLimit {
+            user: Rate::new(2, 60),
+            room: Rate::new(4, 60),
+        })
+    }
+
+    fn description(&self) -> &'static str {
+        "👤 - get random user"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["nominate"]
+    }
+
+    fn parse(&self, _args: &str, _lang: Language) -> Result<Self::Args, String> {
+        Ok(())
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, _args: Self::Args) -> Result<Option<Response>> {
+        Ok(Some(user(ctx.room, ctx.lang).await.into()))
+    }
+}
+
+pub async fn user(room: &Room, lang: Language) -> String {
     let own_username = env::var("BOT_USERNAME").expect("BOT_USERNAME must be set");
     let members = room.members(RoomMemberships::ACTIVE).await;
 
@@ -28,12 +71,12 @@ pub async fn user(room: &Room) -> String {
                 .map(|n| n.to_string())
                 .unwrap_or_else(|| {
                     error!("fails to select nominee");
-                    "Couldn't find an active user :(".into()
+                    tr(lang, "nominate.nobody").into()
                 })
         }
         Err(err) => {
             error!("failed to get members: {err:?}");
-            "Couldn't find an active user :(".to_string()
+            tr(lang, "nominate.nobody").to_string()
         }
     }
 }
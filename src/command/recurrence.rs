@@ -0,0 +1,379 @@
+use crate::{
+    locale::{ordinal, tr, trf, Language},
+    prelude::*,
+};
+use chrono::{
+    Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike, Weekday,
+};
+use chrono_tz::Tz;
+use regex::Regex;
+use std::str::FromStr;
+
+/// Calendar rule of reminders like `every monday at 9:00`, stored as a subset of iCalendar RRULEs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Recurrence {
+    /// On the weekdays, sorted from monday, all seven for every day.
+    Weekly { days: Vec<Weekday>, time: NaiveTime },
+    /// On the day of the month, `-1` is the last one. Months without the day are skipped.
+    Monthly { day: i32, time: NaiveTime },
+}
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+impl Recurrence {
+    /// Parses what follows `every` in e.g. `every monday at 9:00`, `every weekday at 17:00` or `every 1st of month`.
+    /// Rules without a time are at 9:00.
+    pub fn parse(text: &str) -> Option<Self> {
+        let meridiem = Regex::new(r"(?i)([0-9])\s+(am|pm)\b").expect("regex is valid");
+        let text = meridiem.replace_all(text, "$1$2").to_lowercase();
+
+        let mut days = Vec::new();
+        let mut month_day = None;
+        let mut time = None;
+        let mut previous = "";
+        for word in text
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|w| !w.is_empty())
+        {
+            // a bare number is a time only after `at`, `every 2 days` is an interval
+            let is_time = previous == "at"
+                || word.contains(':')
+                || word.ends_with("am")
+                || word.ends_with("pm");
+            previous = word;
+            match word {
+                "and" | "every" | "at" | "on" | "of" | "the" | "month" => {}
+                "day" => days.extend(WEEKDAYS),
+                "weekday" | "weekdays" => days.extend(&WEEKDAYS[..5]),
+                "weekend" | "weekends" => days.extend(&WEEKDAYS[5..]),
+                "last" => month_day = Some(-1),
+                word => {
+                    if let Some(day) = parse_weekday(word) {
+                        days.push(day);
+                    } else if let Some(day) = parse_ordinal(word) {
+                        month_day = Some(day);
+                    } else if is_time {
+                        time = Some(parse_time(word).ok()?);
+                    } else {
+                        return None;
+                    }
+                }
+            }
+        }
+        let time = match time {
+            Some(time) => time,
+            None => NaiveTime::from_hms_opt(9, 0, 0)?,
+        };
+
+        match (month_day, days.is_empty()) {
+            (Some(day), true) => Some(Self::Monthly { day, time }),
+            // `last day of month`
+            (Some(-1), false) if days.len() == 7 => Some(Self::Monthly { day: -1, time }),
+            (None, false) => {
+                days.sort_by_key(Weekday::num_days_from_monday);
+                days.dedup();
+                Some(Self::Weekly { days, time })
+            }
+            _ => None,
+        }
+    }
+
+    fn time(&self) -> NaiveTime {
+        match self {
+            Self::Weekly { time, .. } | Self::Monthly { time, .. } => *time,
+        }
+    }
+
+    fn matches(&self, date: NaiveDate) -> bool {
+        match self {
+            Self::Weekly { days, .. } => days.contains(&date.weekday()),
+            Self::Monthly { day: -1, .. } => date
+                .checked_add_days(Days::new(1))
+                .is_some_and(|next| next.month() != date.month()),
+            Self::Monthly { day, .. } => i64::from(date.day()) == i64::from(*day),
+        }
+    }
+
+    /// The first instant after `after` the rule matches on the clocks of `tz`.
+    pub fn next_after(&self, after: DateTime<Utc>, tz: Tz) -> Option<DateTime<Utc>> {
+        let start = after.with_timezone(&tz).date_naive();
+
+        // the longest gap is between two 31st of a month
+        (0..=62)
+            .filter_map(|n| start.checked_add_days(Days::new(n)))
+            .filter(|date| self.matches(*date))
+            .map(|date| localize(tz, date.and_time(self.time())))
+            .find(|instant| *instant > after)
+    }
+
+    /// E.g. `every Monday, Wednesday at 09:00` in the given language.
+    pub fn describe(&self, lang: Language) -> String {
+        let time = self.time().format("%H:%M").to_string();
+        match self {
+            Self::Weekly { days, .. } if days.len() == 7 => {
+                trf(lang, "recurrence.daily", &[("time", &time)])
+            }
+            Self::Weekly { days, .. } if days[..] == WEEKDAYS[..5] => {
+                trf(lang, "recurrence.weekdays", &[("time", &time)])
+            }
+            Self::Weekly { days, .. } => {
+                let days = days
+                    .iter()
+                    .map(|d| tr(lang, &format!("weekday.{}", d.num_days_from_monday())))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                trf(
+                    lang,
+                    "recurrence.weekly",
+                    &[("days", &days), ("time", &time)],
+                )
+            }
+            Self::Monthly { day: -1, .. } => {
+                trf(lang, "recurrence.last_of_month", &[("time", &time)])
+            }
+            Self::Monthly { day, .. } => trf(
+                lang,
+                "recurrence.monthly",
+                &[("day", &ordinal(lang, *day)), ("time", &time)],
+            ),
+        }
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    // `mondays` and `mon` as well
+    let word = word.trim_end_matches('s');
+    WEEKDAYS.into_iter().find(|d| {
+        let name = d.to_string().to_lowercase();
+        word.len() >= 2 && (name.starts_with(word) || full_name(*d).starts_with(word))
+    })
+}
+
+fn full_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// Parses `1st`, `22nd` or `3.` into a day of the month.
+fn parse_ordinal(word: &str) -> Option<i32> {
+    let re = Regex::new(r"^([0-9]{1,2})(st|nd|rd|th|\.)$").expect("regex is valid");
+    let day = re.captures(word)?[1].parse().ok()?;
+
+    (1..=31).contains(&day).then_some(day)
+}
+
+/// The RRULE, e.g. `FREQ=WEEKLY;BYDAY=MO,WE;BYHOUR=9;BYMINUTE=0`.
+impl Display for Recurrence {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let time = self.time();
+        match self {
+            Self::Weekly { days, .. } if days.len() == 7 => write!(f, "FREQ=DAILY")?,
+            Self::Weekly { days, .. } => {
+                let days = days
+                    .iter()
+                    .map(|d| d.to_string()[..2].to_uppercase())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                write!(f, "FREQ=WEEKLY;BYDAY={days}")?
+            }
+            Self::Monthly { day, .. } => write!(f, "FREQ=MONTHLY;BYMONTHDAY={day}")?,
+        }
+
+        write!(f, ";BYHOUR={};BYMINUTE={}", time.hour(), time.minute())
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut freq = None;
+        let mut days = None;
+        let mut month_day = None;
+        let (mut hour, mut minute) = (9, 0);
+        for part in s.trim_start_matches("RRULE:").split(';') {
+            let Some((key, value)) = part.split_once('=') else {
+                bail!("invalid RRULE part {part}");
+            };
+            match key {
+                "FREQ" => freq = Some(value),
+                "BYDAY" => {
+                    days = Some(
+                        value
+                            .split(',')
+                            .map(|d| parse_weekday(&d.to_lowercase()).context("invalid BYDAY"))
+                            .collect::<Result<Vec<_>>>()?,
+                    )
+                }
+                "BYMONTHDAY" => month_day = Some(value.parse().context("invalid BYMONTHDAY")?),
+                "BYHOUR" => hour = value.parse().context("invalid BYHOUR")?,
+                "BYMINUTE" => minute = value.parse().context("invalid BYMINUTE")?,
+                _ => bail!("unsupported RRULE part {part}"),
+            }
+        }
+        let time = NaiveTime::from_hms_opt(hour, minute, 0).context("invalid time in RRULE")?;
+
+        match (freq, days, month_day) {
+            (Some("DAILY"), None, None) => Ok(Self::Weekly {
+                days: WEEKDAYS.to_vec(),
+                time,
+            }),
+            (Some("WEEKLY"), Some(mut days), None) if !days.is_empty() => {
+                days.sort_by_key(Weekday::num_days_from_monday);
+                days.dedup();
+                Ok(Self::Weekly { days, time })
+            }
+            (Some("MONTHLY"), None, Some(day)) if day == -1 || (1..=31).contains(&day) => {
+                Ok(Self::Monthly { day, time })
+            }
+            _ => bail!("unsupported RRULE {s}"),
+        }
+    }
+}
+
+impl TryFrom<String> for Recurrence {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<Recurrence> for String {
+    fn from(recurrence: Recurrence) -> Self {
+        recurrence.to_string()
+    }
+}
+
+/// The instant the clocks in `tz` show `local`.
+/// Times skipped by a DST change are moved forward by the skipped hour, repeated times are the earlier one.
+pub fn localize(tz: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    match tz.from_local_datetime(&local).earliest() {
+        Some(t) => t.with_timezone(&Utc),
+        None => {
+            // in a gap, so the offset of a few hours earlier is the one before the change
+            let before = local - chrono::Duration::hours(3);
+            let offset = tz
+                .offset_from_local_datetime(&before)
+                .earliest()
+                .map(|o| o.fix())
+                .unwrap_or_else(|| tz.offset_from_utc_datetime(&local).fix());
+            (local - offset).and_utc()
+        }
+    }
+}
+
+/// Parses `17:30`, `17`, `8am` or `8:15pm`.
+pub fn parse_time(text: &str) -> Result<NaiveTime> {
+    let re = Regex::new(r"^(?P<hour>[0-9]{1,2})(:(?P<minute>[0-9]{2}))?(?P<meridiem>am|pm)?$")?;
+    let Some(caps) = re.captures(text) else {
+        bail!("invalid time {text}")
+    };
+
+    let mut hour: u32 = caps["hour"].parse()?;
+    let minute: u32 = caps.name("minute").map_or("0", |m| m.as_str()).parse()?;
+    match caps.name("meridiem").map(|m| m.as_str()) {
+        Some(_) if hour == 0 || hour > 12 => bail!("invalid hour {hour} on a 12-hour clock"),
+        Some("am") => hour %= 12,
+        Some(_) => hour = hour % 12 + 12,
+        None => {}
+    }
+
+    NaiveTime::from_hms_opt(hour, minute, 0).with_context(|| format!("invalid time {text}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        text.parse().expect("valid datetime")
+    }
+
+    #[test]
+    pub fn parse_recurrence() {
+        let cases = [
+            ("monday at 9:00", "FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0"),
+            (
+                "Mon, wed and fri 8 am",
+                "FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8;BYMINUTE=0",
+            ),
+            (
+                "weekday at 17:00",
+                "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR;BYHOUR=17;BYMINUTE=0",
+            ),
+            ("day at 7:30", "FREQ=DAILY;BYHOUR=7;BYMINUTE=30"),
+            (
+                "1st of month",
+                "FREQ=MONTHLY;BYMONTHDAY=1;BYHOUR=9;BYMINUTE=0",
+            ),
+            (
+                "15. of month at 10",
+                "FREQ=MONTHLY;BYMONTHDAY=15;BYHOUR=10;BYMINUTE=0",
+            ),
+            (
+                "last day of the month at 18:00",
+                "FREQ=MONTHLY;BYMONTHDAY=-1;BYHOUR=18;BYMINUTE=0",
+            ),
+        ];
+        for (text, rrule) in cases {
+            let recurrence = Recurrence::parse(text).expect(text);
+            assert_eq!(recurrence.to_string(), rrule, "{text}");
+            assert_eq!(rrule.parse::<Recurrence>().expect(rrule), recurrence);
+        }
+
+        for text in [
+            "2 days",
+            "2 day",
+            "1-3d",
+            "monday at noon",
+            "32nd of month",
+            "hour",
+        ] {
+            assert_eq!(Recurrence::parse(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    pub fn next_occurrence() {
+        let monday = Recurrence::parse("monday at 9:00").unwrap();
+        // 2026-10-18 is a sunday, Berlin is UTC+2 until 2026-10-25
+        assert_eq!(
+            monday.next_after(at("2026-10-18T12:00:00Z"), Tz::Europe__Berlin),
+            Some(at("2026-10-19T07:00:00Z"))
+        );
+        // the week after, Berlin is UTC+1
+        assert_eq!(
+            monday.next_after(at("2026-10-19T07:00:00Z"), Tz::Europe__Berlin),
+            Some(at("2026-10-26T08:00:00Z"))
+        );
+
+        let last = Recurrence::parse("last day of month").unwrap();
+        assert_eq!(
+            last.next_after(at("2027-02-01T00:00:00Z"), Tz::UTC),
+            Some(at("2027-02-28T09:00:00Z"))
+        );
+
+        let thirty_first = Recurrence::parse("31st").unwrap();
+        assert_eq!(
+            thirty_first.next_after(at("2026-11-01T00:00:00Z"), Tz::UTC),
+            Some(at("2026-12-31T09:00:00Z"))
+        );
+    }
+}
@@ -1,248 +1,2397 @@
-use crate::prelude::*;
+use crate::{
+    command::{
+        async_trait,
+        catch_up::{CatchUp, Missed},
+        hours::{self, Hours, Quiet},
+        ics,
+        recurrence::{localize, parse_time, Recurrence},
+        response::{code, escape},
+        span::{describe_range, Span},
+        Command, Ctx, Registry, Response,
+    },
+    config::Config,
+    locale::{normalize_reminder, tr, trf, trf_html, Language},
+    matrix::fetch_message,
+    prelude::*,
+    settings::{self, RoomSettings},
+};
+use chrono::{Datelike, Days, NaiveDate, NaiveTime, Timelike};
+use chrono_tz::Tz;
+use futures::StreamExt;
+use matrix_sdk::{
+    attachment::AttachmentConfig,
+    ruma::{
+        events::{room::message::MessageType, Mentions},
+        EventId, OwnedTransactionId, OwnedUserId, TransactionId, UserId,
+    },
+    Room,
+};
+use mime::Mime;
+use rand::Rng;
 use regex::Regex;
-use std::cmp::Ordering;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    sync::Arc,
+};
+use surrealdb::{Action, Notification};
 use tokio::time::{sleep, Duration as TokioDuration};
 
-pub async fn new(room_id: &RoomId, text: &str, db: &Surreal<Any>) -> Result<String> {
-    match Reminder::try_from_str(text, room_id) {
-        Ok(reminder) => {
-            let db_res: Vec<Reminder> = db.create("reminder").content(&reminder).await?;
-            info!("⏲️ reminder created: {db_res:?}");
-            Ok(format!("Reminder created: {}", reminder))
+/// Recurring reminders more often than once a minute would flood rooms.
+const MIN_RECURRING_SPAN: Span = Span {
+    months: 0,
+    seconds: 60,
+};
+
+/// Sends of a reminder's time are given up after this many failed attempts.
+const MAX_ATTEMPTS: u32 = 10;
+
+/// Name of the file `!reminders export` uploads.
+const EXPORT_FILE_NAME: &str = "reminders.ics";
+
+/// Calendars for `!reminders import` up to 1 MiB are downloaded.
+const MAX_IMPORT_BYTES: usize = 1024 * 1024;
+
+/// Events after this many in a calendar are skipped, so an import can't flood the room with reminders.
+const MAX_IMPORT_EVENTS: usize = 100;
+
+/// Steps through the past times of an imported rule to count them, longer rules are skipped.
+const MAX_IMPORT_STEPS: u32 = 100_000;
+
+/// Snoozes without a span, like reacting with ⏰, last 15 minutes.
+pub const DEFAULT_SNOOZE: Span = Span {
+    months: 0,
+    seconds: 15 * 60,
+};
+
+pub fn register(registry: &mut Registry) {
+    registry.register(NewReminder);
+    registry.register(ListReminders);
+    registry.register(EditReminder);
+    registry.register(PauseReminder);
+    registry.register(ResumeReminder);
+    registry.register(PauseAllReminders);
+    registry.register(ResumeAllReminders);
+    registry.register(DeleteReminder);
+    registry.register(DeleteAllReminders);
+}
+
+pub struct NewReminder;
+
+#[async_trait]
+impl Command for NewReminder {
+    type Args = (Vec<String>, String);
+
+    fn name(&self) -> &'static str {
+        "reminder"
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["remind"]
+    }
+
+    fn description(&self) -> &'static str {
+        "⏲️ - reminder [seconds to years], recurring 🔁 or random 🔀"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &[
+            "reminder 10 minutes: Check the oven",
+            "reminder 2 hours: Laundry is done",
+            "reminder 10 days: Mow the lawn",
+            "reminder 1h30m: Pizza is ready",
+            "reminder every 42 days: Get a haircut.",
+            "reminder 1-3d: Go to the gym every 1-3 days",
+            "reminder at 17:30: Standup",
+            "reminder tomorrow 8am: Call mom",
+            "reminder on 2026-12-24 18:00: Gifts",
+            "reminder every monday at 9:00: Standup",
+            "reminder every weekday at 17:00: Timesheets",
+            "reminder every 1st of month: Pay rent",
+            "reminder every 1d until 2026-12-01: Take pills",
+            "reminder every 2h 5 times: Stretch",
+            "reminder @alice 2h: Water the plants",
+        ]
+    }
+
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String> {
+        let (mentions, text) = split_mentions(args);
+        Ok((mentions, normalize_reminder(lang, text)))
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, (mentions, text): Self::Args) -> Result<Option<Response>> {
+        let mut user_ids = Vec::with_capacity(mentions.len());
+        for mention in mentions {
+            let Some(user_id) = resolve_mention(&mention, ctx.sender) else {
+                return Ok(Some(
+                    trf(ctx.lang, "reminder.unknown_user", &[("user", &mention)]).into(),
+                ));
+            };
+            user_ids.push(user_id);
+        }
+
+        let tz = settings::timezone(ctx.sender, ctx.settings, ctx.db).await?;
+        Ok(Some(
+            new(
+                ctx.room.room_id(),
+                ctx.sender,
+                &user_ids,
+                &text,
+                ctx.prefix,
+                ctx.lang,
+                tz,
+                ctx.db,
+            )
+            .await?
+            .into(),
+        ))
+    }
+}
+
+pub struct ListReminders;
+
+pub enum Listing {
+    List,
+    /// Uploads the reminders as an iCalendar file.
+    Export,
+    /// Creates reminders from the iCalendar file the message replies to.
+    Import,
+}
+
+#[async_trait]
+impl Command for ListReminders {
+    type Args = Listing;
+
+    fn name(&self) -> &'static str {
+        "reminders"
+    }
+
+    fn description(&self) -> &'static str {
+        "⏲️📋 - list all reminders, export or import them as .ics"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["reminders", "reminders export", "reminders import"]
+    }
+
+    fn parse(&self, args: &str, _lang: Language) -> Result<Self::Args, String> {
+        Ok(match args.trim().to_lowercase().as_str() {
+            "export" | "exportieren" => Listing::Export,
+            "import" | "importieren" => Listing::Import,
+            _ => Listing::List,
+        })
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, listing: Self::Args) -> Result<Option<Response>> {
+        let tz = settings::timezone(ctx.sender, ctx.settings, ctx.db).await?;
+        match listing {
+            Listing::List => Ok(Some(
+                list(ctx.room.room_id(), ctx.prefix, ctx.lang, tz, ctx.db).await?,
+            )),
+            // an edit would upload or create the reminders again
+            Listing::Export | Listing::Import if ctx.edit => Ok(None),
+            Listing::Export => export(ctx.room, ctx.prefix, ctx.lang, tz, ctx.db).await,
+            Listing::Import => {
+                let Some(reply_to) = ctx.reply_to else {
+                    return Ok(Some(tr(ctx.lang, "reminder.import_no_file").into()));
+                };
+                Ok(Some(
+                    import(ctx.room, reply_to, ctx.sender, ctx.lang, tz, ctx.db)
+                        .await?
+                        .into(),
+                ))
+            }
+        }
+    }
+}
+
+pub struct DeleteReminder;
+
+#[async_trait]
+impl Command for DeleteReminder {
+    type Args = Target;
+
+    fn name(&self) -> &'static str {
+        "deletereminder"
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &'static str {
+        "⏲️🗑️ - delete reminder by its id"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["deleteReminder kfz"]
+    }
+
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String> {
+        split_target(args).map(|(target, _)| target).ok_or_else(|| {
+            warn!("fails to parse reminder from text: {args}");
+            target_error(args, lang, "reminder.which")
+        })
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, target: Self::Args) -> Result<Option<Response>> {
+        let tz = settings::timezone(ctx.sender, ctx.settings, ctx.db).await?;
+        let moderator = settings::may_change(ctx.room, ctx.sender).await?;
+        delete(
+            ctx.room.room_id(),
+            &target,
+            ctx.sender,
+            moderator,
+            ctx.lang,
+            tz,
+            ctx.db,
+        )
+        .await
+    }
+}
+
+pub struct EditReminder;
+
+/// Changes of `!editreminder`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    /// `title: Drink water` keeps the schedule.
+    Title(String),
+    /// `every 3h` keeps the title.
+    Schedule(String),
+    /// `every 3h: Drink water` like a new reminder.
+    Full(String),
+}
+
+impl Edit {
+    /// Parses e.g. `kfz every 3h: Drink water` to the reminder and the changes.
+    fn parse(args: &str, lang: Language) -> Option<(Target, Self)> {
+        let (target, text) = split_target(args)?;
+
+        let edit = match split_title(text) {
+            None if text.is_empty() => return None,
+            None => Self::Schedule(normalize_reminder(lang, text)),
+            Some((spec, title))
+                if ["title", tr(lang, "reminder.title_word")]
+                    .contains(&spec.trim().to_lowercase().as_str()) =>
+            {
+                Self::Title(title.trim().to_string())
+            }
+            Some(_) => Self::Full(normalize_reminder(lang, text)),
+        };
+
+        Some((target, edit))
+    }
+}
+
+#[async_trait]
+impl Command for EditReminder {
+    type Args = (Target, Edit);
+
+    fn name(&self) -> &'static str {
+        "editreminder"
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &'static str {
+        "⏲️✏️ - change reminder by its id"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &[
+            "editReminder kfz every 3h: Drink water",
+            "editReminder kfz every 3h",
+            "editReminder kfz title: Drink more water",
+        ]
+    }
+
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String> {
+        Edit::parse(args, lang).ok_or_else(|| {
+            warn!("fails to parse reminder edit from text: {args}");
+            target_error(args, lang, "reminder.which_edit")
+        })
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, (target, changes): Self::Args) -> Result<Option<Response>> {
+        let tz = settings::timezone(ctx.sender, ctx.settings, ctx.db).await?;
+        let moderator = settings::may_change(ctx.room, ctx.sender).await?;
+        edit(
+            ctx.room.room_id(),
+            &target,
+            &changes,
+            ctx.sender,
+            moderator,
+            ctx.lang,
+            tz,
+            ctx.db,
+        )
+        .await
+    }
+}
+
+/// A reminder in commands like `!deletereminder kfz`, by its short id.
+/// Numbers in the list aren't taken, they shift when reminders before them are created or deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target(String);
+
+/// Splits the reminder off e.g. `kfz every 3h`.
+fn split_target(args: &str) -> Option<(Target, &str)> {
+    let args = args.trim();
+    let (first, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let first = first.strip_prefix('#').unwrap_or(first).to_lowercase();
+
+    is_short_id(&first).then(|| (Target(first), rest.trim()))
+}
+
+/// The answer to arguments without a reminder, the `which` message or a pointer to the ids for numbers in the list.
+fn target_error(args: &str, lang: Language, which: &str) -> String {
+    let args = args.trim();
+    let position = args
+        .strip_prefix('#')
+        .unwrap_or(args)
+        .starts_with(|c: char| c.is_ascii_digit());
+
+    match position {
+        true => tr(lang, "reminder.by_id").to_string(),
+        false => tr(lang, which).to_string(),
+    }
+}
+
+pub struct PauseReminder;
+
+#[async_trait]
+impl Command for PauseReminder {
+    type Args = (Target, String);
+
+    fn name(&self) -> &'static str {
+        "pausereminder"
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &'static str {
+        "⏲️⏸️ - pause reminder by its id"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["pauseReminder kfz", "pauseReminder kfz until 2026-01-07"]
+    }
+
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String> {
+        split_target(args)
+            .map(|(target, until)| (target, normalize_reminder(lang, until)))
+            .ok_or_else(|| target_error(args, lang, "reminder.which_pause"))
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, (target, until): Self::Args) -> Result<Option<Response>> {
+        let tz = settings::timezone(ctx.sender, ctx.settings, ctx.db).await?;
+        let until = match until.is_empty() {
+            true => None,
+            false => match parse_until(&until, Utc::now(), tz) {
+                Ok(until) => Some(until),
+                Err(_) => return Ok(Some(tr(ctx.lang, "reminder.until_invalid").into())),
+            },
+        };
+        let moderator = settings::may_change(ctx.room, ctx.sender).await?;
+        pause(
+            ctx.room.room_id(),
+            &target,
+            until,
+            ctx.sender,
+            moderator,
+            ctx.lang,
+            tz,
+            ctx.db,
+        )
+        .await
+    }
+}
+
+pub struct ResumeReminder;
+
+#[async_trait]
+impl Command for ResumeReminder {
+    type Args = Target;
+
+    fn name(&self) -> &'static str {
+        "resumereminder"
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &'static str {
+        "⏲️▶️ - resume reminder by its id"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["resumeReminder kfz"]
+    }
+
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String> {
+        split_target(args)
+            .map(|(target, _)| target)
+            .ok_or_else(|| target_error(args, lang, "reminder.which_resume"))
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, target: Self::Args) -> Result<Option<Response>> {
+        let tz = settings::timezone(ctx.sender, ctx.settings, ctx.db).await?;
+        let moderator = settings::may_change(ctx.room, ctx.sender).await?;
+        resume(
+            ctx.room.room_id(),
+            &target,
+            ctx.sender,
+            moderator,
+            ctx.lang,
+            tz,
+            ctx.db,
+        )
+        .await
+    }
+}
+
+pub struct PauseAllReminders;
+
+#[async_trait]
+impl Command for PauseAllReminders {
+    type Args = String;
+
+    fn name(&self) -> &'static str {
+        "pauseallreminders"
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &'static str {
+        "⏲️⏸️ - pause all reminders, e.g. during holidays"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["pauseAllReminders", "pauseAllReminders until 2026-01-07"]
+    }
+
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String> {
+        Ok(normalize_reminder(lang, args.trim()))
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, until: Self::Args) -> Result<Option<Response>> {
+        let tz = settings::timezone(ctx.sender, ctx.settings, ctx.db).await?;
+        let until = match until.is_empty() {
+            true => None,
+            false => match parse_until(&until, Utc::now(), tz) {
+                Ok(until) => Some(until),
+                Err(_) => return Ok(Some(tr(ctx.lang, "reminder.until_invalid").into())),
+            },
+        };
+        // everyone else only pauses their own reminders
+        let creator = match settings::may_change(ctx.room, ctx.sender).await? {
+            true => None,
+            false => Some(ctx.sender),
+        };
+        Ok(Some(
+            pause_all(ctx.room.room_id(), until, creator, ctx.lang, tz, ctx.db)
+                .await?
+                .into(),
+        ))
+    }
+}
+
+pub struct ResumeAllReminders;
+
+#[async_trait]
+impl Command for ResumeAllReminders {
+    type Args = ();
+
+    fn name(&self) -> &'static str {
+        "resumeallreminders"
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &'static str {
+        "⏲️▶️ - resume all paused reminders"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["resumeAllReminders"]
+    }
+
+    fn parse(&self, _args: &str, _lang: Language) -> Result<Self::Args, String> {
+        Ok(())
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, _args: Self::Args) -> Result<Option<Response>> {
+        // everyone else only resumes their own reminders
+        let creator = match settings::may_change(ctx.room, ctx.sender).await? {
+            true => None,
+            false => Some(ctx.sender),
+        };
+        Ok(Some(
+            resume_all(ctx.room.room_id(), creator, ctx.lang, ctx.db)
+                .await?
+                .into(),
+        ))
+    }
+}
+
+pub struct DeleteAllReminders;
+
+#[async_trait]
+impl Command for DeleteAllReminders {
+    type Args = ();
+
+    fn name(&self) -> &'static str {
+        "deleteallreminders"
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["deleteallreminder"]
+    }
+
+    fn description(&self) -> &'static str {
+        "⏲️🗑️ - delete all reminders"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["deleteAllReminders"]
+    }
+
+    fn parse(&self, _args: &str, _lang: Language) -> Result<Self::Args, String> {
+        Ok(())
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, _args: Self::Args) -> Result<Option<Response>> {
+        // everyone else only deletes their own reminders
+        let creator = match settings::may_change(ctx.room, ctx.sender).await? {
+            true => None,
+            false => Some(ctx.sender),
+        };
+        Ok(Some(
+            delete_all(ctx.room.room_id(), creator, ctx.lang, ctx.db)
+                .await?
+                .into(),
+        ))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn new(
+    room_id: &RoomId,
+    creator: &UserId,
+    mentions: &[OwnedUserId],
+    text: &str,
+    prefix: &str,
+    lang: Language,
+    tz: Tz,
+    db: &Surreal<Any>,
+) -> Result<String> {
+    let quiet = Quiet::of(&RoomSettings::load(room_id, db).await?);
+    match Reminder::try_from_str(text, room_id, tz, &quiet) {
+        Ok(reminder) if reminder.send_at.is_some_and(|t| t <= Utc::now()) => {
+            info!("⏲️ reminder {reminder} is in the past");
+            Ok(tr(lang, "reminder.past").to_string())
+        }
+        Ok(mut reminder) => {
+            reminder.creator = Some(creator.to_string());
+            reminder.mentions = mentions.iter().map(ToString::to_string).collect();
+            create(&mut reminder, db).await?;
+            info!("⏲️ reminder created: {reminder:?}");
+            Ok(trf(
+                lang,
+                "reminder.created",
+                &[
+                    ("id", &reminder.short_id.as_deref().unwrap_or_default()),
+                    ("reminder", &reminder.describe(lang, tz)),
+                ],
+            ))
         }
         Err(err) => {
             warn!("fails to parse reminder from {text}, error: {err:?}");
-            Ok("Sorry, I don't know how to parse that reminder.\nUse the !botto command to get some hints.".to_string())
+            Ok(trf(
+                lang,
+                "reminder.invalid",
+                &[("help", &format!("{prefix}botto"))],
+            ))
         }
     }
 }
 
-pub async fn list(room_id: &RoomId, db: &Surreal<Any>) -> Result<String> {
+pub async fn list(
+    room_id: &RoomId,
+    prefix: &str,
+    lang: Language,
+    tz: Tz,
+    db: &Surreal<Any>,
+) -> Result<Response> {
     let reminders: Vec<Reminder> = db
         .query("select * from reminder where room_id = $room_id order by created_at asc")
         .bind(("room_id", room_id.to_string()))
         .await?
         .take(0)?;
 
-    if reminders.is_empty() {
-        return Ok(
-            "No reminders found. Create one with e.g.\n\n!reminder 2 days: Take out the trash.\n\nOr use the !botto command to get more info."
-                .to_string(),
-        );
+    if reminders.is_empty() {
+        let example = format!("{prefix}{}", tr(lang, "reminder.example"));
+        let help = format!("{prefix}botto");
+        return Ok(Response::new(
+            trf(
+                lang,
+                "reminder.none",
+                &[("example", &example), ("help", &help)],
+            ),
+            trf_html(
+                lang,
+                "reminder.none",
+                &[("example", &code(&example)), ("help", &code(&help))],
+            ),
+        ));
+    }
+
+    info!("⏲️ list {} reminders", reminders.len());
+
+    let header = tr(lang, "reminder.list");
+    let mut body = header.to_string();
+    let mut html = format!("{}<ol>", escape(header));
+    for (n, r) in reminders.iter().enumerate() {
+        let mut text = r.describe(lang, tz);
+        if r.paused {
+            let paused = match r.paused_until {
+                Some(until) => trf_at(lang, "reminder.list_paused_until", until, tz, &[]),
+                None => tr(lang, "reminder.list_paused").to_string(),
+            };
+            text = format!("⏸️ {text} ({paused})");
+        } else if let (None, Some(next)) = (r.send_at, r.next_send_at) {
+            // absolute reminders already show when they are sent
+            text.push_str(&format!(" ({})", next_send(next, lang, tz)));
+        }
+        if r.delivery.state == DeliveryState::Failed {
+            text = format!("⚠️ {text} ({})", tr(lang, "reminder.list_failed"));
+        }
+        let short_id = r.short_id.as_deref().unwrap_or_default();
+        body.push_str(&format!("\n{}. [{short_id}] {text}", n + 1));
+        html.push_str(&format!("<li>{} {}</li>", code(short_id), escape(&text)));
+    }
+    html.push_str("</ol>");
+
+    Ok(Response::new(body, html))
+}
+
+/// Uploads the room's reminders as an iCalendar file, see [`Reminder::to_event`].
+pub async fn export(
+    room: &Room,
+    prefix: &str,
+    lang: Language,
+    tz: Tz,
+    db: &Surreal<Any>,
+) -> Result<Option<Response>> {
+    let reminders: Vec<Reminder> = db
+        .query("select * from reminder where room_id = $room_id order by created_at asc")
+        .bind(("room_id", room.room_id().to_string()))
+        .await?
+        .take(0)?;
+    if reminders.is_empty() {
+        return Ok(Some(list(room.room_id(), prefix, lang, tz, db).await?));
+    }
+
+    let events: Vec<ics::Event> = reminders.iter().filter_map(|r| r.to_event(lang)).collect();
+    let calendar = ics::write(&events, Utc::now());
+    let content_type: Mime = "text/calendar".parse()?;
+    room.send_attachment(
+        EXPORT_FILE_NAME,
+        &content_type,
+        calendar.into_bytes(),
+        AttachmentConfig::new(),
+    )
+    .await
+    .context("fails to upload reminders")?;
+
+    info!("⏲️📤 {} reminders exported", events.len());
+
+    Ok(Some(
+        trf(lang, "reminder.exported", &[("count", &events.len())]).into(),
+    ))
+}
+
+/// Creates reminders of `creator` from the iCalendar file of the message `file_event`, see [`Reminder::from_event`].
+pub async fn import(
+    room: &Room,
+    file_event: &EventId,
+    creator: &UserId,
+    lang: Language,
+    tz: Tz,
+    db: &Surreal<Any>,
+) -> Result<String> {
+    let event = fetch_message(room, file_event)
+        .await
+        .with_context(|| format!("fails to get event {file_event}"))?;
+    let MessageType::File(file) = event.content.msgtype else {
+        return Ok(tr(lang, "reminder.import_no_file").to_string());
+    };
+    let name = file
+        .filename
+        .as_deref()
+        .unwrap_or(&file.body)
+        .to_lowercase();
+    let info = file.info.as_deref();
+    let is_calendar =
+        name.ends_with(".ics") || info.and_then(|i| i.mimetype.as_deref()) == Some("text/calendar");
+    if !is_calendar {
+        return Ok(tr(lang, "reminder.import_no_file").to_string());
+    }
+    let too_large = || {
+        trf(
+            lang,
+            "reminder.import_too_large",
+            &[("max", &(MAX_IMPORT_BYTES / 1024))],
+        )
+    };
+    if info
+        .and_then(|i| i.size)
+        .is_some_and(|size| u64::from(size) > MAX_IMPORT_BYTES as u64)
+    {
+        return Ok(too_large());
+    }
+
+    let data = room
+        .client()
+        .media()
+        .get_file(file, false)
+        .await
+        .context("fails to download calendar")?
+        .unwrap_or_default();
+    if data.len() > MAX_IMPORT_BYTES {
+        return Ok(too_large());
+    }
+    let events = match String::from_utf8(data)
+        .map_err(anyhow::Error::from)
+        .and_then(|text| ics::parse(&text))
+    {
+        Ok(events) => events,
+        Err(err) => {
+            warn!("fails to parse calendar {file_event}, error: {err:?}");
+            return Ok(tr(lang, "reminder.import_invalid").to_string());
+        }
+    };
+
+    let now = Utc::now();
+    let (mut imported, mut skipped) = (0, 0);
+    for event in events {
+        match event.and_then(|event| Reminder::from_event(&event, room.room_id(), tz, now)) {
+            Ok(_) if imported >= MAX_IMPORT_EVENTS => skipped += 1,
+            Ok(mut reminder) => {
+                reminder.creator = Some(creator.to_string());
+                create(&mut reminder, db).await?;
+                debug!("reminder imported: {reminder:?}");
+                imported += 1;
+            }
+            Err(err) => {
+                debug!("skipping event of calendar {file_event}, error: {err:?}");
+                skipped += 1;
+            }
+        }
+    }
+
+    info!("⏲️📥 {imported} reminders imported, {skipped} events skipped");
+
+    Ok(trf(
+        lang,
+        "reminder.imported",
+        &[("count", &imported), ("skipped", &skipped)],
+    ))
+}
+
+/// Deletes the room's reminders, only the ones of `creator` if given.
+pub async fn delete_all(
+    room_id: &RoomId,
+    creator: Option<&UserId>,
+    lang: Language,
+    db: &Surreal<Any>,
+) -> Result<String> {
+    let Some(creator) = creator else {
+        db.query("delete from reminder where room_id = $room_id")
+            .bind(("room_id", room_id.to_string()))
+            .await?;
+
+        info!("⏲️🗑️ All reminders deleted for room: {room_id}");
+
+        return Ok(tr(lang, "reminder.all_deleted").to_string());
+    };
+
+    db.query("delete from reminder where room_id = $room_id and creator = $creator")
+        .bind(("room_id", room_id.to_string()))
+        .bind(("creator", creator.to_string()))
+        .await?;
+
+    info!("⏲️🗑️ All reminders of {creator} deleted for room: {room_id}");
+
+    Ok(tr(lang, "reminder.all_own_deleted").to_string())
+}
+
+/// Deletes the reminder `target` if `sender` created it or is a `moderator`.
+pub async fn delete(
+    room_id: &RoomId,
+    target: &Target,
+    sender: &UserId,
+    moderator: bool,
+    lang: Language,
+    tz: Tz,
+    db: &Surreal<Any>,
+) -> Result<Option<Response>> {
+    let Some(reminder) = find(room_id, target, db).await? else {
+        return Ok(None);
+    };
+    if !moderator && !reminder.created_by(sender) {
+        info!("⏲️🚫 {sender} may not delete reminder {reminder}");
+        return Ok(Some(tr(lang, "reminder.forbidden").into()));
+    }
+
+    let reminder: Option<Reminder> = db.delete(&reminder.id).await?;
+
+    if let Some(reminder) = reminder {
+        info!("⏲️🗑️ Reminder deleted: {reminder}");
+        Ok(Some(
+            trf(
+                lang,
+                "reminder.deleted",
+                &[("reminder", &reminder.describe(lang, tz))],
+            )
+            .into(),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Changes the reminder `target` if `sender` created it or is a `moderator`.
+#[allow(clippy::too_many_arguments)]
+pub async fn edit(
+    room_id: &RoomId,
+    target: &Target,
+    edit: &Edit,
+    sender: &UserId,
+    moderator: bool,
+    lang: Language,
+    tz: Tz,
+    db: &Surreal<Any>,
+) -> Result<Option<Response>> {
+    let Some(reminder) = find(room_id, target, db).await? else {
+        return Ok(None);
+    };
+    if !moderator && !reminder.created_by(sender) {
+        info!("⏲️🚫 {sender} may not edit reminder {reminder}");
+        return Ok(Some(tr(lang, "reminder.forbidden").into()));
+    }
+
+    let quiet = Quiet::of(&RoomSettings::load(room_id, db).await?);
+    let edited = match reminder.edited(edit, tz, &quiet) {
+        Ok(edited) if edited.send_at.is_some_and(|t| t <= Utc::now()) => {
+            return Ok(Some(tr(lang, "reminder.past").into()));
+        }
+        Ok(edited) => edited,
+        Err(err) => {
+            warn!("fails to edit reminder {reminder} with {edit:?}, error: {err:?}");
+            return Ok(Some(tr(lang, "reminder.edit_invalid").into()));
+        }
+    };
+
+    // merging keeps `created_at`, so the reminder stays in its place in the list
+    let _: Option<Reminder> = db.update(&reminder.id).merge(&edited).await?;
+    info!("⏲️✏️ reminder edited: {reminder} -> {edited}");
+
+    Ok(Some(
+        trf(
+            lang,
+            "reminder.edited",
+            &[("reminder", &edited.describe(lang, tz))],
+        )
+        .into(),
+    ))
+}
+
+/// Saves a new reminder with a short id that's unique in its room.
+async fn create(reminder: &mut Reminder, db: &Surreal<Any>) -> Result<()> {
+    let mut attempts = 0;
+    loop {
+        reminder.short_id = Some(new_short_id());
+        let created: Result<Vec<Reminder>, _> = db.create("reminder").content(&*reminder).await;
+        match created {
+            Ok(_) => return Ok(()),
+            // the short id is taken in the room, which the unique index refuses
+            Err(err) if attempts < 5 => {
+                debug!("fails to create reminder, retrying with another short id, error: {err:?}");
+                attempts += 1;
+            }
+            Err(err) => return Err(err).context("fails to create reminder"),
+        }
+    }
+}
+
+/// Letters for short ids, without `l` and `o` that look like `1` and `0`.
+const SHORT_ID_LETTERS: &[u8] = b"abcdefghijkmnpqrstuvwxyz";
+const SHORT_ID_LEN: usize = 3;
+
+/// A random id like `kfz`, short enough to type in `!deletereminder kfz`.
+fn new_short_id() -> String {
+    let mut rng = rand::thread_rng();
+    (0..SHORT_ID_LEN)
+        .map(|_| char::from(SHORT_ID_LETTERS[rng.gen_range(0..SHORT_ID_LETTERS.len())]))
+        .collect()
+}
+
+fn is_short_id(text: &str) -> bool {
+    text.len() == SHORT_ID_LEN && text.bytes().all(|b| SHORT_ID_LETTERS.contains(&b))
+}
+
+/// Pauses the reminder `target`, until `until` if given.
+#[allow(clippy::too_many_arguments)]
+pub async fn pause(
+    room_id: &RoomId,
+    target: &Target,
+    until: Option<DateTime<Utc>>,
+    sender: &UserId,
+    moderator: bool,
+    lang: Language,
+    tz: Tz,
+    db: &Surreal<Any>,
+) -> Result<Option<Response>> {
+    let Some(reminder) = find(room_id, target, db).await? else {
+        return Ok(None);
+    };
+    if !moderator && !reminder.created_by(sender) {
+        info!("⏲️🚫 {sender} may not pause reminder {reminder}");
+        return Ok(Some(tr(lang, "reminder.forbidden").into()));
+    }
+
+    db.query("update $reminder set paused = true, paused_until = $until")
+        .bind(("reminder", &reminder.id))
+        .bind(("until", until))
+        .await?
+        .check()
+        .context("fails to pause reminder")?;
+    info!("⏸️ reminder paused until {until:?}: {reminder}");
+
+    let description = reminder.describe(lang, tz);
+    let text = match until {
+        Some(until) => trf_at(
+            lang,
+            "reminder.paused_until",
+            until,
+            tz,
+            &[("reminder", &description)],
+        ),
+        None => trf(lang, "reminder.paused", &[("reminder", &description)]),
+    };
+
+    Ok(Some(text.into()))
+}
+
+/// Resumes the reminder `target`.
+pub async fn resume(
+    room_id: &RoomId,
+    target: &Target,
+    sender: &UserId,
+    moderator: bool,
+    lang: Language,
+    tz: Tz,
+    db: &Surreal<Any>,
+) -> Result<Option<Response>> {
+    let Some(reminder) = find(room_id, target, db).await? else {
+        return Ok(None);
+    };
+    if !moderator && !reminder.created_by(sender) {
+        info!("⏲️🚫 {sender} may not resume reminder {reminder}");
+        return Ok(Some(tr(lang, "reminder.forbidden").into()));
+    }
+
+    resume_one(&reminder, db).await?;
+
+    Ok(Some(
+        trf(
+            lang,
+            "reminder.resumed",
+            &[("reminder", &reminder.describe(lang, tz))],
+        )
+        .into(),
+    ))
+}
+
+/// Pauses the room's reminders, only the ones of `creator` if given.
+pub async fn pause_all(
+    room_id: &RoomId,
+    until: Option<DateTime<Utc>>,
+    creator: Option<&UserId>,
+    lang: Language,
+    tz: Tz,
+    db: &Surreal<Any>,
+) -> Result<String> {
+    let paused: Vec<Reminder> = db
+        .query("update reminder set paused = true, paused_until = $until where room_id = $room_id and ($creator = none or creator = $creator)")
+        .bind(("room_id", room_id.to_string()))
+        .bind(("until", until))
+        .bind(("creator", creator.map(ToString::to_string)))
+        .await?
+        .take(0)
+        .context("fails to pause reminders")?;
+    info!(
+        "⏸️ {} reminders paused in room {room_id} until {until:?}",
+        paused.len()
+    );
+
+    let count = paused.len();
+    Ok(match until {
+        Some(until) => trf_at(
+            lang,
+            "reminder.all_paused_until",
+            until,
+            tz,
+            &[("count", &count)],
+        ),
+        None => trf(lang, "reminder.all_paused", &[("count", &count)]),
+    })
+}
+
+/// Resumes the room's paused reminders, only the ones of `creator` if given.
+pub async fn resume_all(
+    room_id: &RoomId,
+    creator: Option<&UserId>,
+    lang: Language,
+    db: &Surreal<Any>,
+) -> Result<String> {
+    let paused: Vec<Reminder> = db
+        .query("select * from reminder where room_id = $room_id and paused = true and ($creator = none or creator = $creator)")
+        .bind(("room_id", room_id.to_string()))
+        .bind(("creator", creator.map(ToString::to_string)))
+        .await?
+        .take(0)?;
+    for reminder in &paused {
+        resume_one(reminder, db).await?;
+    }
+
+    Ok(trf(
+        lang,
+        "reminder.all_resumed",
+        &[("count", &paused.len())],
+    ))
+}
+
+async fn resume_one(reminder: &Reminder, db: &Surreal<Any>) -> Result<()> {
+    let room_id = RoomId::parse(&reminder.room_id)?;
+    let quiet = Quiet::of(&RoomSettings::load(&room_id, db).await?);
+    let next_send_at = reminder.resumed_next(Utc::now(), &quiet);
+    // a reminder that couldn't be sent is tried again
+    db.query(
+        "update $reminder set paused = false, paused_until = none, next_send_at = $next_send_at, delivery = $delivery",
+    )
+    .bind(("reminder", &reminder.id))
+    .bind(("next_send_at", next_send_at))
+    .bind(("delivery", Delivery::default()))
+    .await?
+    .check()
+    .context("fails to resume reminder")?;
+    info!("▶️ reminder resumed, next at {next_send_at:?}: {reminder}");
+
+    Ok(())
+}
+
+/// The room's reminder with the short id of `target`.
+async fn find(room_id: &RoomId, target: &Target, db: &Surreal<Any>) -> Result<Option<Reminder>> {
+    debug!("target: {target:?}");
+
+    let reminder: Option<Reminder> = db
+        .query("select * from reminder where room_id = $room_id and short_id = $short_id")
+        .bind(("room_id", room_id.to_string()))
+        .bind(("short_id", &target.0))
+        .await?
+        .take(0)?;
+
+    Ok(reminder)
+}
+
+/// Wait before waking a reminder again whose handling failed, e.g. because the db was unreachable.
+const RETRY_AFTER_ERROR: chrono::Duration = chrono::Duration::minutes(1);
+
+/// Longest sleep of the scheduler, so changes of the system clock are noticed.
+const MAX_SLEEP: TokioDuration = TokioDuration::from_secs(60 * 60);
+
+/// Sends reminders when they are due, sleeping until the next one instead of polling the db.
+/// A live query on the `reminder` table reschedules on creates, edits and deletes from any command.
+pub async fn notify(
+    db: Surreal<Any>,
+    matrix_client: Client,
+    registry: Arc<Registry>,
+    config: Arc<Config>,
+) -> Result<()> {
+    loop {
+        // a lost connection ends the live query, so everything is loaded again
+        if let Err(err) = schedule(&db, &matrix_client, &registry, &config).await {
+            warn!("reminder scheduler stopped, restarting, error: {err:?}");
+        }
+        sleep(TokioDuration::from_secs(5)).await;
+    }
+}
+
+async fn schedule(
+    db: &Surreal<Any>,
+    matrix_client: &Client,
+    registry: &Registry,
+    config: &Config,
+) -> Result<()> {
+    // subscribing before loading misses no changes in between
+    let mut changes = db
+        .select::<Vec<Reminder>>("reminder")
+        .live()
+        .await
+        .context("fails to start live query of reminders")?;
+    let reminders: Vec<Reminder> = db
+        .select("reminder")
+        .await
+        .context("fails to load reminders")?;
+
+    let mut schedule = Schedule::default();
+    for r in &reminders {
+        schedule.push(r);
+    }
+    info!("⏰ scheduled {} reminders", schedule.len());
+
+    loop {
+        let wait = match schedule.next_wake() {
+            Some(at) => (at - Utc::now())
+                .to_std()
+                .unwrap_or_default()
+                .min(MAX_SLEEP),
+            None => MAX_SLEEP,
+        };
+
+        tokio::select! {
+            change = changes.next() => {
+                match change.context("live query of reminders ended")? {
+                    Ok(Notification { action: Action::Delete, data, .. }) => schedule.remove(&data.id),
+                    Ok(Notification { data, .. }) => {
+                        trace!("rescheduling reminder {}", data.id);
+                        schedule.push(&data);
+                    }
+                    Err(err) => warn!("fails to read change of reminders, error: {err:?}"),
+                }
+            }
+            _ = sleep(wait) => {
+                for id in schedule.pop_due(Utc::now()) {
+                    // the db has the latest state, e.g. of a reminder paused in the meantime
+                    let reminder: Option<Reminder> = db.select(&id).await?;
+                    let Some(r) = reminder else {
+                        continue;
+                    };
+                    if let Err(err) = wake(r, db, matrix_client, registry, config).await {
+                        warn!("fails to handle due reminder {id}, error: {err:?}");
+                        schedule.push_at(id, Utc::now() + RETRY_AFTER_ERROR);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Sends the reminder or ends its pause if it's due, the live query then schedules its next time.
+/// The reminder only moves on once the homeserver accepted its notification, failed sends are retried with backoff.
+async fn wake(
+    r: Reminder,
+    db: &Surreal<Any>,
+    matrix_client: &Client,
+    registry: &Registry,
+    config: &Config,
+) -> Result<()> {
+    let now = Utc::now();
+    if r.paused {
+        // pauses like `!pauseallreminders until 2026-01-07` end by themselves
+        if r.paused_until.is_some_and(|until| until <= now) {
+            resume_one(&r, db).await?;
+        }
+        return Ok(());
+    }
+    let Some(due) = r.next_send_at.filter(|next| *next <= now) else {
+        return Ok(());
+    };
+    if r.delivery.retry_at.is_some_and(|at| at > now) {
+        return Ok(());
+    }
+
+    let room_id = RoomId::parse(&r.room_id)?;
+    let settings = RoomSettings::load(&room_id, db).await?;
+    let quiet = Quiet::of(&settings);
+    let Some(room) = matrix_client.get_room(&room_id) else {
+        warn!("room {room_id} not found to send reminder");
+        return failed(&r, now, &quiet, db).await;
+    };
+    let lang = settings.language.unwrap_or(config.language);
+
+    // reminders missed while the bot was offline are handled by the room's policy,
+    // retries already passed it and are late by their backoff
+    let catch_up = match r.delivery.attempts {
+        0 => settings.catch_up.unwrap_or_default(),
+        _ => CatchUp::Late,
+    };
+    let late = match catch_up.missed(due, now) {
+        Missed::OnTime => None,
+        Missed::Late(late) => Some(late),
+        Missed::Skipped => {
+            info!("⏭️ skipping reminder missed since {due}: {r}");
+            return advance(&r, now, DeliveryState::Pending, &quiet, db).await;
+        }
+    };
+
+    // the transaction id is saved before sending, so a resend after a crash isn't duplicated by the homeserver
+    let txn_id: OwnedTransactionId = match &r.delivery.txn_id {
+        Some(txn_id) => txn_id.as_str().into(),
+        None => {
+            let txn_id = TransactionId::new();
+            db.query("update $reminder set delivery.txn_id = $txn_id")
+                .bind(("reminder", &r.id))
+                .bind(("txn_id", txn_id.to_string()))
+                .await?
+                .check()
+                .context("fails to save transaction id of reminder")?;
+            txn_id
+        }
+    };
+
+    // send reminder notification
+
+    let content = notification(&r, &room, late, lang).await;
+    info!(
+        "🔔 sending reminder '{}' to room {room_id}, attempt {}",
+        r.title,
+        r.delivery.attempts + 1
+    );
+    let sent = match room.send(content).with_transaction_id(&txn_id).await {
+        Ok(sent) => sent,
+        Err(err) => {
+            warn!("fails to send reminder to room {room_id}, error: {err:?}");
+            return failed(&r, now, &quiet, db).await;
+        }
+    };
+    advance(&r, now, DeliveryState::Sent, &quiet, db).await?;
+
+    // reactions and replies to the notification snooze it
+    if let Err(err) = save_notification(&room_id, &sent.event_id, &r, db).await {
+        warn!(
+            "fails to save notification of reminder {}, error: {err:?}",
+            r.id
+        );
+    }
+
+    // a reminder like `!choose gym, run, swim` runs the command, the bot ignores its own message
+    if let Err(err) = run_chained(&r, &room, db, registry, config).await {
+        warn!("fails to run command of reminder {}, error: {err:?}", r.id);
+    }
+
+    Ok(())
+}
+
+/// Moves the reminder on to its next time after `now` with its time's delivery `state`, `Pending` if it was skipped.
+/// One-off reminders are done unless they failed, which keeps them listed to be resumed, recurring ones after their end.
+async fn advance(
+    r: &Reminder,
+    now: DateTime<Utc>,
+    state: DeliveryState,
+    quiet: &Quiet,
+    db: &Surreal<Any>,
+) -> Result<()> {
+    debug!("updating reminder {r} in db");
+    let (next_send_at, remaining) = r.following(now, quiet);
+    let query = match (next_send_at, state) {
+        (Some(_), DeliveryState::Sent) => {
+            "update $reminder set last_sent_at = time::now(), next_send_at = $next_send_at, remaining = $remaining, delivery = $delivery"
+        }
+        (Some(_), _) => {
+            "update $reminder set next_send_at = $next_send_at, remaining = $remaining, delivery = $delivery"
+        }
+        (None, DeliveryState::Failed) if !r.recurring => {
+            "update $reminder set next_send_at = none, delivery = $delivery"
+        }
+        // recurring reminders are done after their last time
+        (None, _) => "delete $reminder",
+    };
+    db.query(query)
+        .bind(("reminder", &r.id))
+        .bind(("next_send_at", next_send_at))
+        .bind(("remaining", remaining))
+        .bind((
+            "delivery",
+            Delivery {
+                state,
+                ..Delivery::default()
+            },
+        ))
+        .await?
+        .check()
+        .with_context(|| format!("fails to update reminder {} in db", r.id))?;
+
+    Ok(())
+}
+
+/// Records a failed attempt to send the reminder, which is tried again after a backoff or given up.
+async fn failed(r: &Reminder, now: DateTime<Utc>, quiet: &Quiet, db: &Surreal<Any>) -> Result<()> {
+    let Some(delivery) = r.delivery.retried(now) else {
+        warn!(
+            "⚠️ giving up on reminder after {} attempts: {r}",
+            r.delivery.attempts + 1
+        );
+        return advance(r, now, DeliveryState::Failed, quiet, db).await;
+    };
+
+    info!(
+        "🔁 retrying reminder at {:?}: {r}",
+        delivery.retry_at.unwrap_or(now)
+    );
+    db.query("update $reminder set delivery = $delivery")
+        .bind(("reminder", &r.id))
+        .bind(("delivery", delivery))
+        .await?
+        .check()
+        .context("fails to record failed delivery of reminder")?;
+
+    Ok(())
+}
+
+/// The message of a fired reminder, mentioning its creator and the users it names.
+async fn notification(
+    reminder: &Reminder,
+    room: &Room,
+    late: Option<Span>,
+    lang: Language,
+) -> RoomMessageEventContent {
+    let bells = match late {
+        Some(late) => format!(
+            "🔔🔔🔔 {}",
+            trf(lang, "reminder.late", &[("span", &late.describe(lang))])
+        ),
+        None => "🔔🔔🔔".to_string(),
+    };
+
+    let mut user_ids: Vec<OwnedUserId> = Vec::new();
+    for user_id in reminder.creator.iter().chain(&reminder.mentions) {
+        match UserId::parse(user_id.as_str()) {
+            Ok(user_id) if !user_ids.contains(&user_id) => user_ids.push(user_id),
+            Ok(_) => {}
+            Err(err) => warn!("fails to parse user id {user_id} of reminder, error: {err:?}"),
+        }
+    }
+    if user_ids.is_empty() {
+        return RoomMessageEventContent::text_plain(format!("{}\n{bells}", reminder.title));
+    }
+
+    let mut body = format!("{}\n{bells}", reminder.title);
+    let mut html = format!("{}<br>{}", escape(&reminder.title), escape(&bells));
+    for user_id in &user_ids {
+        let name = match room.get_member_no_sync(user_id).await {
+            Ok(Some(member)) => member.name().to_string(),
+            _ => user_id.to_string(),
+        };
+        body.push_str(&format!(" {name}"));
+        html.push_str(&format!(
+            " <a href=\"{}\">{}</a>",
+            user_id.matrix_to_uri(),
+            escape(&name)
+        ));
+    }
+
+    RoomMessageEventContent::text_html(body, html).add_mentions(Mentions::with_user_ids(user_ids))
+}
+
+/// Splits leading mentions like `@alice @bob:example.org` off a reminder.
+fn split_mentions(text: &str) -> (Vec<String>, &str) {
+    let mut mentions = Vec::new();
+    let mut rest = text.trim_start();
+    while rest.starts_with('@') {
+        let (mention, after) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        mentions.push(mention.to_string());
+        rest = after.trim_start();
+    }
+
+    (mentions, rest)
+}
+
+/// The user of `@alice:example.org`, or of `@alice` on the homeserver of `sender`.
+fn resolve_mention(mention: &str, sender: &UserId) -> Option<OwnedUserId> {
+    let user_id = match mention.contains(':') {
+        true => mention.to_string(),
+        false => format!("{mention}:{}", sender.server_name()),
+    };
+
+    UserId::parse(user_id).ok()
+}
+
+async fn save_notification(
+    room_id: &RoomId,
+    event_id: &EventId,
+    reminder: &Reminder,
+    db: &Surreal<Any>,
+) -> Result<()> {
+    db.query("create type::thing('notification', $event_id) set room_id = $room_id, reminder = $reminder, title = $title")
+        .bind(("event_id", event_id.to_string()))
+        .bind(("room_id", room_id.to_string()))
+        .bind(("reminder", &reminder.id))
+        .bind(("title", &reminder.title))
+        .await?
+        .check()
+        .context("fails to create notification")?;
+
+    Ok(())
+}
+
+/// Parses replies like `snooze` or `snooze 1h` to a notification, snoozing for 15 minutes by default.
+pub fn parse_snooze(text: &str, lang: Language) -> Option<Span> {
+    let text = text.trim().to_lowercase();
+    let rest = ["snooze", tr(lang, "reminder.snooze_word")]
+        .into_iter()
+        .find_map(|word| text.strip_prefix(word))?;
+
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    match rest.trim() {
+        "" => Some(DEFAULT_SNOOZE),
+        rest => match Span::parse_range(&normalize_reminder(lang, rest))? {
+            (false, span, None) => Some(span),
+            _ => None,
+        },
+    }
+}
+
+/// Sends the reminder of the notification event again after the span, `None` if the event is no notification.
+pub async fn snooze(
+    room_id: &RoomId,
+    notification_event_id: &EventId,
+    span: Span,
+    user_id: &UserId,
+    tz: Tz,
+    lang: Language,
+    db: &Surreal<Any>,
+) -> Result<Option<String>> {
+    let title: Option<String> = db
+        .query("select value title from type::thing('notification', $event_id) where room_id = $room_id")
+        .bind(("event_id", notification_event_id.to_string()))
+        .bind(("room_id", room_id.to_string()))
+        .await?
+        .take(0)
+        .context("fails to select notification")?;
+    let Some(title) = title else {
+        return Ok(None);
+    };
+    let Some(send_at) = span.after(Utc::now(), tz) else {
+        bail!("snooze of {span:?} is out of range");
+    };
+
+    let mut reminder = Reminder {
+        id: Thing::from(("reminder", Uuid::new_v4().to_string().as_str())),
+        short_id: None,
+        room_id: room_id.to_string(),
+        title,
+        min_span: None,
+        max_span: None,
+        recurring: false,
+        send_at: Some(send_at),
+        recurrence: None,
+        timezone: Some(tz),
+        snoozed: true,
+        creator: Some(user_id.to_string()),
+        mentions: Vec::new(),
+        paused: false,
+        paused_until: None,
+        hours: None,
+        ends_at: None,
+        remaining: None,
+        last_sent_at: None,
+        next_send_at: Some(send_at),
+        delivery: Delivery::default(),
+    };
+    create(&mut reminder, db).await?;
+    info!("💤 reminder snoozed: {reminder}");
+
+    Ok(Some(trf(
+        lang,
+        "reminder.snoozed",
+        &[("span", &span.describe(lang)), ("title", &reminder.title)],
+    )))
+}
+
+/// Runs the command in the reminder's title as its creator, so it has the creator's rights and not the bot's.
+async fn run_chained(
+    r: &Reminder,
+    room: &Room,
+    db: &Surreal<Any>,
+    registry: &Registry,
+    config: &Config,
+) -> Result<()> {
+    let title = r.title.as_str();
+    let Some(sender) = r.chain_sender() else {
+        debug!("not running command of reminder {} without creator", r.id);
+        return Ok(());
+    };
+    let settings = RoomSettings::load(room.room_id(), db).await?;
+    let ctx = Ctx {
+        room,
+        db,
+        registry,
+        config,
+        settings: &settings,
+        sender: &sender,
+        edit: false,
+        reply_to: None,
+        prefix: settings.prefix.as_deref().unwrap_or(&config.prefix),
+        lang: settings.language.unwrap_or(config.language),
+    };
+
+    if let Some(resp) = registry.dispatch(&ctx, title).await? {
+        info!("🔗 sending response to command of reminder '{title}'");
+        room.send(resp.into_content())
+            .await
+            .context("fails to send response")?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Reminder {
+    id: Thing,
+    /// Unique in the room, e.g. `kfz`, set when the reminder is saved.
+    short_id: Option<String>,
+    room_id: String,
+    title: String,
+    /// Interval of relative reminders like `every 1h30m`.
+    min_span: Option<Span>,
+    /// Longest interval of reminders at random times like `1-3 days`.
+    max_span: Option<Span>,
+    recurring: bool,
+    /// Instant of reminders at a specific date and time like `at 17:30`.
+    send_at: Option<DateTime<Utc>>,
+    /// Rule of calendar reminders like `every monday at 9:00`, evaluated by [`notify`].
+    recurrence: Option<Recurrence>,
+    /// The creator's timezone, `recurrence` and months of spans are on its clock.
+    timezone: Option<Tz>,
+    /// Whether it's the follow-up of a snoozed notification.
+    #[serde(default)]
+    snoozed: bool,
+    /// The sender of `!reminder`, unknown for reminders from before creators were recorded.
+    creator: Option<String>,
+    /// Other users mentioned when it fires.
+    #[serde(default)]
+    mentions: Vec<String>,
+    /// Paused reminders aren't sent, until `paused_until` if set.
+    #[serde(default)]
+    paused: bool,
+    paused_until: Option<DateTime<Utc>>,
+    /// Hours on the room's clock that reminders at random times like `1-3d between 9:00 and 18:00` are sent in.
+    hours: Option<Hours>,
+    /// Recurring reminders like `every 1d until 2026-12-01` end after this instant.
+    ends_at: Option<DateTime<Utc>>,
+    /// Times left of recurring reminders like `every 2h 5 times`, counting down as they pass.
+    remaining: Option<u32>,
+    last_sent_at: Option<DateTime<Utc>>,
+    next_send_at: Option<DateTime<Utc>>,
+    /// Delivery of the time at `next_send_at`, or of the last one once it's sent or given up.
+    #[serde(default)]
+    delivery: Delivery,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Delivery {
+    state: DeliveryState,
+    /// Failed attempts to send the current time.
+    attempts: u32,
+    retry_at: Option<DateTime<Utc>>,
+    /// The same for all attempts, so the homeserver drops resends of a notification it already accepted.
+    txn_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum DeliveryState {
+    /// Not sent yet.
+    #[default]
+    Pending,
+    Sent,
+    /// Given up after [`MAX_ATTEMPTS`].
+    Failed,
+}
+
+impl Delivery {
+    /// The delivery after a failed attempt at `now`, `None` once there were [`MAX_ATTEMPTS`].
+    fn retried(&self, now: DateTime<Utc>) -> Option<Self> {
+        let attempts = self.attempts + 1;
+        if attempts >= MAX_ATTEMPTS {
+            return None;
+        }
+
+        Some(Self {
+            state: DeliveryState::Pending,
+            attempts,
+            retry_at: Some(now + backoff(attempts)),
+            txn_id: self.txn_id.clone(),
+        })
+    }
+}
+
+/// Wait before the next attempt, from 30 seconds doubling up to an hour.
+fn backoff(attempts: u32) -> chrono::Duration {
+    let seconds = 30i64 << attempts.saturating_sub(1).min(7);
+    chrono::Duration::seconds(seconds).min(chrono::Duration::hours(1))
+}
+
+impl Reminder {
+    /// Parses the arguments of `!reminder`, dates and times are in the timezone `tz`.
+    /// Random times are drawn outside the room's `quiet` hours.
+    fn try_from_str(text: &str, room_id: &RoomId, tz: Tz, quiet: &Quiet) -> Result<Self> {
+        if let Some((spec, title)) = split_title(text) {
+            let now = Utc::now();
+            if let Some((spec, hours)) = split_hours(spec) {
+                let text = format!("{spec}:{title}");
+                let mut reminder = Self::try_from_str(&text, room_id, tz, quiet)?;
+                if reminder.max_span.is_none() {
+                    bail!("only reminders at random times can have hours");
+                }
+                reminder.hours = Some(hours);
+                // drawn again inside the hours
+                reminder.next_send_at = reminder
+                    .next_after(now, quiet)
+                    .filter(|next| !reminder.ended(*next));
+                if reminder.next_send_at.is_none() {
+                    bail!("reminder ends before it's sent");
+                }
+
+                return Ok(reminder);
+            }
+            if let Some((spec, end)) = split_end(spec, now, tz) {
+                let text = format!("{spec}:{title}");
+                let mut reminder = Self::try_from_str(&text, room_id, tz, quiet)?;
+                if !reminder.recurring {
+                    bail!("only recurring reminders can end");
+                }
+                if end.at.is_some_and(|at| at <= now) {
+                    bail!("reminder must end in the future");
+                }
+                reminder.ends_at = end.at;
+                reminder.remaining = end.times;
+                reminder.next_send_at = reminder.next_send_at.filter(|next| !reminder.ended(*next));
+                if reminder.next_send_at.is_none() {
+                    bail!("reminder ends before it's sent");
+                }
+
+                return Ok(reminder);
+            }
+        }
+
+        if let Some((spec, title)) = split_title(text) {
+            let title = title.trim();
+            let (first_word, rest) = spec
+                .trim()
+                .split_once(char::is_whitespace)
+                .unwrap_or((spec.trim(), ""));
+            let first_word = first_word.to_lowercase();
+
+            let absolute = ["at", "on", "today", "tomorrow"].contains(&first_word.as_str());
+            let recurrence = Some(rest)
+                .filter(|_| first_word == "every")
+                .and_then(Recurrence::parse);
+            if absolute || recurrence.is_some() {
+                if title.is_empty() || title.chars().count() > 200 {
+                    bail!("reminder title must have 1 to 200 characters")
+                }
+
+                let now = Utc::now();
+                let (send_at, next_send_at) = match &recurrence {
+                    Some(recurrence) => (None, recurrence.next_after(now, tz)),
+                    None => {
+                        let send_at = parse_instant(spec, now, tz)?;
+                        (Some(send_at), Some(send_at))
+                    }
+                };
+
+                return Ok(Reminder {
+                    id: Thing::from(("reminder", Uuid::new_v4().to_string().as_str())),
+                    short_id: None,
+                    room_id: room_id.to_string(),
+                    title: title.to_string(),
+                    min_span: None,
+                    max_span: None,
+                    recurring: recurrence.is_some(),
+                    send_at,
+                    timezone: Some(tz),
+                    snoozed: false,
+                    creator: None,
+                    mentions: Vec::new(),
+                    paused: false,
+                    paused_until: None,
+                    recurrence,
+                    hours: None,
+                    ends_at: None,
+                    remaining: None,
+                    last_sent_at: None,
+                    next_send_at,
+                    delivery: Delivery::default(),
+                });
+            }
+        }
+
+        let Some((spec, title)) = split_title(text) else {
+            bail!("reminder title is required")
+        };
+        let title = title.trim();
+        if title.is_empty() || title.chars().count() > 200 {
+            bail!("reminder title must have 1 to 200 characters")
+        }
+
+        let Some((recurring, min, max)) = Span::parse_range(spec) else {
+            bail!("invalid reminder interval {spec}")
+        };
+        if recurring && min < MIN_RECURRING_SPAN {
+            bail!("recurring reminders must be at least a minute apart")
+        }
+
+        let mut reminder = Reminder {
+            id: Thing::from(("reminder", Uuid::new_v4().to_string().as_str())),
+            short_id: None,
+            room_id: room_id.to_string(),
+            title: title.to_string(),
+            min_span: Some(min),
+            max_span: max,
+            recurring,
+            send_at: None,
+            recurrence: None,
+            timezone: Some(tz),
+            snoozed: false,
+            creator: None,
+            mentions: Vec::new(),
+            paused: false,
+            paused_until: None,
+            hours: None,
+            ends_at: None,
+            remaining: None,
+            last_sent_at: None,
+            next_send_at: None,
+            delivery: Delivery::default(),
+        };
+        reminder.next_send_at = reminder.next_after(Utc::now(), quiet);
+
+        debug!("new reminder: {reminder:?}");
+
+        Ok(reminder)
+    }
+
+    /// When the reminder is sent after the one at `now`, random spans are drawn again each time outside the `quiet` hours.
+    fn next_after(&self, now: DateTime<Utc>, quiet: &Quiet) -> Option<DateTime<Utc>> {
+        let tz = self.timezone.unwrap_or(Tz::UTC);
+        if let Some(recurrence) = &self.recurrence {
+            return recurrence.next_after(now, tz);
+        }
+
+        let min = self.min_span?.after(now, tz)?;
+        // spans mixing months and days may be in either order once on the calendar
+        let Some(max) = self
+            .max_span
+            .and_then(|max| max.after(now, tz))
+            .filter(|max| *max > min)
+        else {
+            return Some(min);
+        };
+
+        Some(hours::draw(min, max, self.hours, quiet))
+    }
+}
+
+/// Reminders by when they are due next, the earliest first.
+#[derive(Debug, Default)]
+struct Schedule {
+    queue: BinaryHeap<Reverse<(DateTime<Utc>, Thing)>>,
+    /// The latest time of each reminder, older entries in `queue` are skipped.
+    latest: HashMap<Thing, DateTime<Utc>>,
+}
+
+impl Schedule {
+    fn push(&mut self, reminder: &Reminder) {
+        match reminder.wake_at() {
+            Some(at) => self.push_at(reminder.id.clone(), at),
+            None => self.remove(&reminder.id),
+        }
+    }
+
+    fn push_at(&mut self, id: Thing, at: DateTime<Utc>) {
+        self.latest.insert(id.clone(), at);
+        self.queue.push(Reverse((at, id)));
+    }
+
+    fn remove(&mut self, id: &Thing) {
+        self.latest.remove(id);
+    }
+
+    fn len(&self) -> usize {
+        self.latest.len()
+    }
+
+    /// When the earliest reminder is due.
+    fn next_wake(&mut self) -> Option<DateTime<Utc>> {
+        while let Some(Reverse((at, id))) = self.queue.peek() {
+            if self.latest.get(id) == Some(at) {
+                return Some(*at);
+            }
+            self.queue.pop();
+        }
+
+        None
+    }
+
+    /// Takes the reminders due at `now`.
+    fn pop_due(&mut self, now: DateTime<Utc>) -> Vec<Thing> {
+        let mut due = Vec::new();
+        while self.next_wake().is_some_and(|at| at <= now) {
+            if let Some(Reverse((_, id))) = self.queue.pop() {
+                self.latest.remove(&id);
+                due.push(id);
+            }
+        }
+
+        due
+    }
+}
+
+impl Reminder {
+    /// When the scheduler has to look at the reminder again, to send it, retry it or to end its pause.
+    fn wake_at(&self) -> Option<DateTime<Utc>> {
+        match self.paused {
+            true => self.paused_until,
+            false => self.next_send_at.map(|next| match self.delivery.retry_at {
+                Some(retry_at) => next.max(retry_at),
+                None => next,
+            }),
+        }
+    }
+
+    /// When a resumed reminder is sent, ones missed during the pause are sent once right away instead of in a burst.
+    fn resumed_next(&self, now: DateTime<Utc>, quiet: &Quiet) -> Option<DateTime<Utc>> {
+        match self.next_send_at {
+            Some(next) if next > now => Some(next),
+            _ if self.recurring => self
+                .next_after(now, quiet)
+                .filter(|next| !self.ended(*next)),
+            _ => Some(now),
+        }
+    }
+
+    /// The time after the one at `now` and the times left then, no time once the reminder is done.
+    /// Every time counts, whether it was sent, skipped or given up.
+    fn following(&self, now: DateTime<Utc>, quiet: &Quiet) -> (Option<DateTime<Utc>>, Option<u32>) {
+        let remaining = self.remaining.map(|n| n.saturating_sub(1));
+        if !self.recurring || remaining == Some(0) {
+            return (None, remaining);
+        }
+        let next = self
+            .next_after(now, quiet)
+            .filter(|next| !self.ended(*next));
+
+        (next, remaining)
+    }
+
+    /// Whether a time at `at` is after the end of the reminder.
+    fn ended(&self, at: DateTime<Utc>) -> bool {
+        self.ends_at.is_some_and(|end| at > end)
+    }
+
+    /// Who the command in the title runs as, none for reminders from before creators were recorded.
+    fn chain_sender(&self) -> Option<OwnedUserId> {
+        self.creator.as_deref().and_then(|c| UserId::parse(c).ok())
+    }
+
+    fn created_by(&self, user_id: &UserId) -> bool {
+        self.creator.as_deref() == Some(user_id.as_str())
+    }
+
+    /// The reminder with the changes of `!editreminder`, new schedules are in the timezone `tz` outside the `quiet` hours.
+    fn edited(&self, edit: &Edit, tz: Tz, quiet: &Quiet) -> Result<Self> {
+        let text = match edit {
+            Edit::Title(title) => {
+                let title = title.trim();
+                if title.is_empty() || title.chars().count() > 200 {
+                    bail!("reminder title must have 1 to 200 characters")
+                }
+                return Ok(Self {
+                    title: title.to_string(),
+                    ..self.clone()
+                });
+            }
+            Edit::Schedule(spec) => format!("{spec}: {}", self.title),
+            Edit::Full(text) => text.clone(),
+        };
+
+        let room_id = RoomId::parse(&self.room_id)?;
+        let new = Self::try_from_str(&text, &room_id, tz, quiet)?;
+
+        Ok(Self {
+            id: self.id.clone(),
+            short_id: self.short_id.clone(),
+            creator: self.creator.clone(),
+            mentions: self.mentions.clone(),
+            paused: self.paused,
+            paused_until: self.paused_until,
+            last_sent_at: self.last_sent_at,
+            ..new
+        })
     }
 
-    info!("⏲️ list {} reminders", reminders.len());
+    /// E.g. `every 1 - 3 days: Go to the gym` in the given language.
+    fn describe(&self, lang: Language, tz: Tz) -> String {
+        if let Some(send_at) = self.send_at {
+            let send_at = send_at.with_timezone(&tz);
+            let at = trf(
+                lang,
+                "reminder.at",
+                &[
+                    ("date", &send_at.format(tr(lang, "reminder.date_format"))),
+                    ("time", &send_at.format("%H:%M %Z")),
+                ],
+            );
+            let snoozed = if self.snoozed { "💤 " } else { "" };
+            return format!("{snoozed}{at}: {}", self.title);
+        }
 
-    let mut res = "⏲️ Reminders:".to_string();
-    for (n, r) in reminders.iter().enumerate() {
-        res.push_str(&format!("\n{}. {r}", n + 1));
-    }
+        let end = self.describe_end(lang, tz);
+        if let Some(recurrence) = &self.recurrence {
+            let rule = recurrence.describe(lang);
+            // the rule is on the creator's clock, which is named if it differs from the reader's
+            return match self.timezone.filter(|t| *t != tz) {
+                Some(timezone) => format!("{rule} ({}){end}: {}", timezone.name(), self.title),
+                None => format!("{rule}{end}: {}", self.title),
+            };
+        }
 
-    Ok(res)
-}
+        let recurring = if self.recurring {
+            format!("{} ", tr(lang, "reminder.every"))
+        } else {
+            String::new()
+        };
+        let min = self.min_span.unwrap_or_default();
+        let range = match &self.max_span {
+            Some(max) => describe_range(&min, max, lang),
+            None => min.describe(lang),
+        };
 
-pub async fn delete_all(room_id: &RoomId, db: &Surreal<Any>) -> Result<String> {
-    db.query("delete from reminder where room_id = $room_id")
-        .bind(("room_id", room_id.to_string()))
-        .await?;
+        let hours = match self.hours {
+            Some(hours) => format!(" {}", hours.describe(lang)),
+            None => String::new(),
+        };
+
+        format!("{recurring}{range}{hours}{end}: {}", self.title)
+    }
 
-    info!("⏲️🗑️ All reminders deleted for room: {room_id}");
+    /// E.g. `, until 2026-12-01, 3 more times`, empty for reminders without an end.
+    fn describe_end(&self, lang: Language, tz: Tz) -> String {
+        let mut end = String::new();
+        if let Some(ends_at) = self.ends_at {
+            let until = match ends_at.with_timezone(&tz).time() == end_of_day() {
+                true => trf_at(lang, "reminder.until_date", ends_at, tz, &[]),
+                false => trf_at(lang, "reminder.until", ends_at, tz, &[]),
+            };
+            end.push_str(&format!(", {until}"));
+        }
+        match self.remaining {
+            Some(1) => end.push_str(&format!(", {}", tr(lang, "reminder.once_more"))),
+            Some(count) => end.push_str(&format!(
+                ", {}",
+                trf(lang, "reminder.times_left", &[("count", &count)])
+            )),
+            None => {}
+        }
 
-    Ok("All reminders deleted.".to_string())
+        end
+    }
 }
 
-pub async fn delete(room_id: &RoomId, text: &str, db: &Surreal<Any>) -> Result<Option<String>> {
-    let re = Regex::new(r"([0-9]+)")?;
+impl Reminder {
+    /// The reminder as an event at its next time, recurring ones with an RRULE on the creator's clock.
+    /// Reminders at random times and intervals without an RRULE like `every 1 month 2 days` are one event
+    /// at their next time, with their schedule in the description.
+    fn to_event(&self, lang: Language) -> Option<ics::Event> {
+        // failed one-offs have no next time
+        let next = self.next_send_at.or(self.send_at)?;
+        let tz = self.timezone.unwrap_or(Tz::UTC);
+        let rule = match (&self.recurrence, self.min_span, self.max_span) {
+            _ if !self.recurring => None,
+            (Some(recurrence), _, _) => Some(recurrence.to_string()),
+            (None, Some(span), None) => ics::span_rule(span),
+            _ => None,
+        };
+        // an RRULE can't have both, the times left are the nearer end
+        let rrule = rule.map(|rule| match (self.remaining, self.ends_at) {
+            (Some(count), _) => format!("{rule};COUNT={count}"),
+            (None, Some(until)) => format!("{rule};UNTIL={}", until.format("%Y%m%dT%H%M%SZ")),
+            (None, None) => rule,
+        });
+        let start = match rrule {
+            Some(_) => ics::Start::Local(next.with_timezone(&tz).naive_local(), Some(tz)),
+            None => ics::Start::Utc(next),
+        };
 
-    let Some(index) = re
-        .find_iter(text)
-        .next()
-        .and_then(|m| m.as_str().parse::<usize>().ok())
-    else {
-        warn!("fails to parse index from text: {text}");
-        return Ok(None);
-    };
+        Some(ics::Event {
+            uid: format!("{}@botto", self.id.id.to_raw()),
+            summary: self.title.clone(),
+            description: (self.recurring && rrule.is_none()).then(|| self.describe(lang, tz)),
+            start,
+            rrule,
+        })
+    }
 
-    debug!("index: {index:?}");
+    /// A reminder from an imported event, floating times are in the timezone `tz`.
+    /// Events without an RRULE are one-offs, ones with an RRULE `!reminder` can't express like `BYSETPOS` are refused.
+    fn from_event(
+        event: &ics::Event,
+        room_id: &RoomId,
+        tz: Tz,
+        now: DateTime<Utc>,
+    ) -> Result<Self> {
+        let title = event.summary.trim();
+        if title.is_empty() || title.chars().count() > 200 {
+            bail!("reminder title must have 1 to 200 characters")
+        }
+        let tz = event.start.tz().unwrap_or(tz);
+        // events on a whole day are at 9:00 like rules without a time
+        let default_time = NaiveTime::from_hms_opt(9, 0, 0).context("invalid default time")?;
+        let start = event.start.instant(tz, default_time);
 
-    let reminders: Vec<Reminder> = db
-        .query("select * from reminder where room_id = $room_id order by created_at asc")
-        .bind(("room_id", room_id.to_string()))
-        .await?
-        .take(0)?;
+        let mut reminder = Reminder {
+            id: Thing::from(("reminder", Uuid::new_v4().to_string().as_str())),
+            short_id: None,
+            room_id: room_id.to_string(),
+            title: title.to_string(),
+            min_span: None,
+            max_span: None,
+            recurring: false,
+            send_at: None,
+            recurrence: None,
+            timezone: Some(tz),
+            snoozed: false,
+            creator: None,
+            mentions: Vec::new(),
+            paused: false,
+            paused_until: None,
+            hours: None,
+            ends_at: None,
+            remaining: None,
+            last_sent_at: None,
+            next_send_at: None,
+            delivery: Delivery::default(),
+        };
+        let Some(rrule) = &event.rrule else {
+            if start <= now {
+                bail!("event is in the past");
+            }
+            reminder.send_at = Some(start);
+            reminder.next_send_at = Some(start);
+            return Ok(reminder);
+        };
 
-    debug!("reminder_ids: {reminders:#?}");
+        let mut freq = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by = Vec::new();
+        for part in rrule.split(';') {
+            let Some((key, value)) = part.split_once('=') else {
+                bail!("invalid RRULE part {part}");
+            };
+            match key.to_uppercase().as_str() {
+                "FREQ" => freq = Some(value.to_uppercase()),
+                "INTERVAL" => interval = value.parse().context("invalid INTERVAL")?,
+                "COUNT" => count = Some(value.parse::<u32>().context("invalid COUNT")?),
+                "UNTIL" => {
+                    let end = ics::Start::parse(&[], value)?;
+                    until = Some(end.instant(tz, end_of_day()));
+                }
+                // weeks start on monday, which only matters for intervals of weeks with several days
+                "WKST" => {}
+                key => by.push((key.to_string(), value.to_string())),
+            }
+        }
+        let freq = freq.context("RRULE has no FREQ")?;
 
-    let Some(reminder) = reminders.get(index.saturating_sub(1)) else {
-        debug!(
-            "tried to get reminder by index: {index}, but max index is {}",
-            reminders.len()
-        );
-        return Ok(None);
-    };
+        let local = start.with_timezone(&tz);
+        let (recurrence, span) = match (freq.as_str(), interval) {
+            ("DAILY" | "WEEKLY" | "MONTHLY", 1) => {
+                // the day and time of rules without them are the ones of the first event
+                let has = |key: &str| by.iter().any(|(k, _)| k == key);
+                let mut parts: Vec<String> = by.iter().map(|(k, v)| format!("{k}={v}")).collect();
+                if freq == "WEEKLY" && !has("BYDAY") {
+                    parts.push(format!(
+                        "BYDAY={}",
+                        local.weekday().to_string()[..2].to_uppercase()
+                    ));
+                }
+                if freq == "MONTHLY" && !has("BYMONTHDAY") {
+                    parts.push(format!("BYMONTHDAY={}", local.day()));
+                }
+                if !has("BYHOUR") {
+                    parts.push(format!("BYHOUR={}", local.hour()));
+                }
+                if !has("BYMINUTE") {
+                    parts.push(format!("BYMINUTE={}", local.minute()));
+                }
+                let rule = format!("FREQ={freq};{}", parts.join(";"));
+                (Some(rule.parse::<Recurrence>()?), None)
+            }
+            _ if by.is_empty() => {
+                let span = ics::rule_span(&freq, interval)
+                    .with_context(|| format!("unsupported FREQ {freq}"))?;
+                if span < MIN_RECURRING_SPAN {
+                    bail!("recurring reminders must be at least a minute apart")
+                }
+                (None, Some(span))
+            }
+            _ => bail!("unsupported RRULE {rrule}"),
+        };
 
-    let reminder: Option<Reminder> = db.delete(&reminder.id).await?;
+        // the times before `now` have passed, they count towards COUNT
+        let step = |at: DateTime<Utc>| match (&recurrence, span) {
+            (Some(recurrence), _) => recurrence.next_after(at, tz),
+            (None, Some(span)) => span.after(at, tz),
+            (None, None) => None,
+        };
+        let mut next = match &recurrence {
+            Some(recurrence) => recurrence.next_after(start - chrono::Duration::seconds(1), tz),
+            None => Some(start),
+        }
+        .context("RRULE has no times")?;
+        let mut passed: u32 = 0;
+        // spans without months have a fixed length, so their past times needn't be stepped through
+        if let Some(span) = span.filter(|span| span.months == 0 && next <= now) {
+            let periods = (now - next).num_seconds().unsigned_abs() / span.seconds + 1;
+            passed = u32::try_from(periods).unwrap_or(u32::MAX);
+            next += chrono::Duration::seconds(i64::try_from(periods * span.seconds)?);
+        }
+        while next <= now {
+            if passed >= MAX_IMPORT_STEPS {
+                bail!("RRULE has too many past times");
+            }
+            next = step(next).context("RRULE has no next time")?;
+            passed += 1;
+        }
 
-    if let Some(reminder) = reminder {
-        info!("⏲️🗑️ Reminder deleted: {reminder}");
-        Ok(Some(format!("Reminder deleted: {reminder}")))
-    } else {
-        Ok(None)
+        if let Some(count) = count {
+            let remaining = count.checked_sub(passed).filter(|n| *n > 0);
+            reminder.remaining = Some(remaining.context("RRULE has ended")?);
+        }
+        if until.is_some_and(|until| next > until) {
+            bail!("RRULE has ended");
+        }
+        reminder.recurring = true;
+        reminder.recurrence = recurrence;
+        reminder.min_span = span;
+        reminder.ends_at = until;
+        reminder.next_send_at = Some(next);
+
+        Ok(reminder)
     }
 }
 
-pub async fn notify(db: Surreal<Any>, matrix_client: Client) -> Result<()> {
-    loop {
-        sleep(TokioDuration::from_secs(1)).await;
-        trace!("checking for due reminders");
-
-        let due_reminders: Vec<Reminder> = db
-            .query("select * from reminder where next_send_at and next_send_at < time::now()")
-            .await?
-            .take(0)?;
-
-        if due_reminders.is_empty() {
-            continue;
-        }
+/// Splits a reminder into its schedule and title at the first colon that isn't part of a time like `17:30`.
+pub fn split_title(text: &str) -> Option<(&str, &str)> {
+    let bytes = text.as_bytes();
+    let index = bytes.iter().enumerate().position(|(i, b)| {
+        *b == b':'
+            && !(i > 0
+                && bytes[i - 1].is_ascii_digit()
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit))
+    })?;
 
-        debug!("found {} due reminders", due_reminders.len());
+    Some((&text[..index], &text[index + 1..]))
+}
 
-        for r in due_reminders {
-            let room_id = RoomId::parse(&r.room_id)?;
-            let Some(room) = matrix_client.get_room(&room_id) else {
-                warn!("room {room_id} not found to send reminder");
-                continue;
-            };
+/// E.g. `next: 2026-10-20 at 14:03 CEST`.
+fn next_send(next: DateTime<Utc>, lang: Language, tz: Tz) -> String {
+    trf_at(lang, "reminder.next", next, tz, &[])
+}
 
-            // update reminder in db
+/// The message `key` with the `date` and `time` of `instant` on the clock of `tz` besides the `args`.
+fn trf_at(
+    lang: Language,
+    key: &str,
+    instant: DateTime<Utc>,
+    tz: Tz,
+    args: &[(&str, &dyn Display)],
+) -> String {
+    let instant = instant.with_timezone(&tz);
+    let date = instant.format(tr(lang, "reminder.date_format"));
+    let time = instant.format("%H:%M %Z");
+    let mut all: Vec<(&str, &dyn Display)> = vec![("date", &date), ("time", &time)];
+    all.extend_from_slice(args);
 
-            debug!("updating reminder {r} in db");
-            let _r = db
-                .query("fn::send_reminder($reminder)")
-                .bind(("reminder", &r.id))
-                .await?
-                .check()
-                .map_err(|err| {
-                    warn!(
-                        "fails to call fn::send_reminder in db on reminder {}, error: {err:?}",
-                        r.id
-                    )
-                });
+    trf(lang, key, &all)
+}
 
-            // send reminder notification
+/// Parses e.g. `at 17:30`, `on 2026-12-24 18:00` or `tomorrow 8am` on the clock of `tz`.
+/// A time without a date is its next occurrence after `now`, a date without a time is at 9:00.
+fn parse_instant(spec: &str, now: DateTime<Utc>, tz: Tz) -> Result<DateTime<Utc>> {
+    let nine = NaiveTime::from_hms_opt(9, 0, 0).context("9:00 is a valid time")?;
+    parse_instant_or(spec, now, tz, nine)
+}
 
-            let content = RoomMessageEventContent::text_plain(format!("{}\n🔔🔔🔔", r.title));
-            info!("🔔 sending reminder '{}' to room {room_id}", r.title);
-            let _ = room
-                .send(content)
-                .await
-                .map_err(|err| warn!("fails to send reminder to room {room_id}, error: {err:?}"));
-        }
+/// Parses the end of pauses like `until 2026-01-07`, a date without a time is at its start.
+fn parse_until(spec: &str, now: DateTime<Utc>, tz: Tz) -> Result<DateTime<Utc>> {
+    // `bis` in German reminders is `to`
+    let spec = match spec.trim().split_once(char::is_whitespace) {
+        Some(("until" | "to", rest)) => rest,
+        _ => spec,
+    };
+    let until = parse_instant_or(spec, now, tz, NaiveTime::MIN)?;
+    if until <= now {
+        bail!("pause must end in the future");
     }
+
+    Ok(until)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Reminder {
-    id: Thing,
-    room_id: String,
-    title: String,
-    interval_unit: String,
-    min_interval: usize,
-    max_interval: Option<usize>,
-    recurring: bool,
-    last_sent_at: Option<DateTime<Utc>>,
-    next_send_at: Option<DateTime<Utc>>,
+/// Splits hours like `between 9:00 and 18:00` off a schedule, wherever they are in it.
+fn split_hours(spec: &str) -> Option<(String, Hours)> {
+    let re = Regex::new(
+        r"(?i)\bbetween\s+(\S+(?:\s*[ap]m)?\s*(?:-|–|\band\b|\bto\b)\s*\S+(?:\s*[ap]m)?)",
+    )
+    .expect("regex is valid");
+    let caps = re.captures(spec)?;
+    let hours = Hours::parse(&caps[1])?;
+    let all = caps.get(0)?;
+    let rest = format!("{} {}", &spec[..all.start()], &spec[all.end()..]);
+
+    Some((rest.trim().to_string(), hours))
 }
 
-impl Reminder {
-    fn try_from_str(text: &str, room_id: &RoomId) -> Result<Self> {
-        let re = Regex::new(
-            r"!reminder([ ]*)(?P<recurring>(?i)[every ]*)(?P<min>[0-9]*)(?P<from_to>(?i)[^(0-9|m|h|d)]*)(?P<max>[0-9]*)(?P<delimiter>(?i)[^(0-9|m|h|d)]*)(?P<unit>(?i)[m|h|d]*)(.*):(?P<msg>.{1,200})",
-        )?;
+/// Splits end conditions like `until 2026-12-01` and `5 times` off a schedule, `None` if it has none.
+/// A date without a time ends at the end of that day.
+fn split_end(spec: &str, now: DateTime<Utc>, tz: Tz) -> Option<(&str, End)> {
+    let (rest, times) = match split_times(spec) {
+        Some((rest, times)) => (rest, Some(times)),
+        None => (spec, None),
+    };
+    let (rest, ends_at) = match split_until(rest, now, tz) {
+        Some((rest, ends_at)) => (rest, Some(ends_at)),
+        None => (rest, None),
+    };
+    // `5 times until tomorrow` works as well
+    let (rest, times) = match (times, split_times(rest)) {
+        (None, Some((rest, times))) => (rest, Some(times)),
+        _ => (rest, times),
+    };
 
-        let Some(groups) = re.captures_iter(text).next() else {
-            bail!("Invalid reminder format")
-        };
+    let end = End { at: ends_at, times };
+    (ends_at.is_some() || times.is_some()).then_some((rest, end))
+}
 
-        let groups = groups
-            .iter()
-            .map(|g| g.unwrap().as_str())
-            .collect::<Vec<&str>>();
+/// End conditions of a recurring reminder, all of them apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct End {
+    at: Option<DateTime<Utc>>,
+    times: Option<u32>,
+}
 
-        let recurring = groups.get(2).map(|s| !s.is_empty()).unwrap_or(false);
-        let Some(min) = groups.get(3) else {
-            bail!("reminder min is required")
-        };
-        debug!("min: {min}");
-        let Some(interval_unit) = groups.get(7).map(|s| s.trim().to_lowercase()) else {
-            bail!("reminder interval_unit is required")
-        };
-        let interval_unit = if interval_unit.contains("m") {
-            "minute"
-        } else if interval_unit.contains("h") {
-            "hour"
-        } else if interval_unit.contains("d") {
-            "day"
-        } else {
-            bail!("reminder interval_unit is invalid")
-        };
-        debug!("interval_unit: {interval_unit}");
+/// Splits e.g. `5 times` off the end of a schedule.
+fn split_times(spec: &str) -> Option<(&str, u32)> {
+    let spec = spec.trim_end();
+    let (rest, word) = spec.rsplit_once(char::is_whitespace)?;
+    if !word.eq_ignore_ascii_case("times") {
+        return None;
+    }
+    let (rest, count) = rest.trim_end().rsplit_once(char::is_whitespace)?;
+    let count = count.parse().ok().filter(|count| *count > 0)?;
 
-        let Some(title) = groups.get(9).map(|s| s.trim()) else {
-            bail!("reminder title is required")
-        };
-        debug!("title: {title}");
-
-        let min: usize = min.trim().parse()?;
-        let max: Option<usize> = groups
-            .get(5)
-            .and_then(|s| {
-                Some(s.trim()).filter(|s| !s.is_empty()).map(|s| {
-                    let max = s.parse().unwrap_or_else(|err| {
-                        warn!("fails to parse {s} to max, {err:?}");
-                        min
-                    });
-                    match max.cmp(&min) {
-                        Ordering::Greater => Some(max),
-                        _ => None,
-                    }
-                })
-            })
-            .flatten();
+    Some((rest, count))
+}
 
-        let reminder = Reminder {
-            id: Thing::from(("reminder", Uuid::new_v4().to_string().as_str())),
-            room_id: room_id.to_string(),
-            title: title.to_string(),
-            interval_unit: interval_unit.to_string(),
-            min_interval: min,
-            max_interval: max,
-            recurring,
-            last_sent_at: None,
-            next_send_at: None,
-        };
+/// Splits e.g. `until 2026-12-01` off the end of a schedule, ranges like `every 10 to 20 days` are no end.
+fn split_until(spec: &str, now: DateTime<Utc>, tz: Tz) -> Option<(&str, DateTime<Utc>)> {
+    // `bis` in German reminders is `to`
+    let words: Vec<_> = spec.match_indices(char::is_whitespace).collect();
+    words.iter().rev().find_map(|(i, _)| {
+        let (rest, end) = spec.split_at(*i);
+        let (word, end) = end.trim_start().split_once(char::is_whitespace)?;
+        if !word.eq_ignore_ascii_case("until") && !word.eq_ignore_ascii_case("to") {
+            return None;
+        }
+        let ends_at = parse_instant_or(end, now, tz, end_of_day()).ok()?;
 
-        debug!("new reminder: {reminder:?}");
+        Some((rest.trim_end(), ends_at))
+    })
+}
 
-        Ok(reminder)
+fn end_of_day() -> NaiveTime {
+    NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN)
+}
+
+/// Like [`parse_instant`], a date without a time is at `default_time`.
+fn parse_instant_or(
+    spec: &str,
+    now: DateTime<Utc>,
+    tz: Tz,
+    default_time: NaiveTime,
+) -> Result<DateTime<Utc>> {
+    let today = now.with_timezone(&tz).date_naive();
+    let mut date = None;
+    let mut time = String::new();
+    for word in spec.split_whitespace().map(str::to_lowercase) {
+        match word.as_str() {
+            "at" | "on" => {}
+            "today" => date = Some(today),
+            "tomorrow" => date = today.checked_add_days(Days::new(1)),
+            word => match NaiveDate::parse_from_str(word, "%Y-%m-%d")
+                .or_else(|_| NaiveDate::parse_from_str(word, "%d.%m.%Y"))
+            {
+                Ok(d) => date = Some(d),
+                // `8 am` is the same as `8am`
+                Err(_) => time.push_str(word),
+            },
+        }
     }
+
+    let time = match (time.is_empty(), date) {
+        (false, _) => parse_time(&time)?,
+        (true, Some(_)) => default_time,
+        (true, None) => bail!("reminder needs a date or a time"),
+    };
+
+    let instant = match date {
+        Some(date) => localize(tz, date.and_time(time)),
+        None => {
+            let at_today = localize(tz, today.and_time(time));
+            if at_today > now {
+                at_today
+            } else {
+                let tomorrow = today
+                    .checked_add_days(Days::new(1))
+                    .context("date is out of range")?;
+                localize(tz, tomorrow.and_time(time))
+            }
+        }
+    };
+
+    Ok(instant)
 }
 
 impl Display for Reminder {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        let recurring = if self.recurring { "every " } else { "" };
-        let range = if let Some(max) = self.max_interval {
-            format!("{} - {} {}s", self.min_interval, max, self.interval_unit)
-        } else {
-            format!(
-                "{} {}{}",
-                self.min_interval,
-                self.interval_unit,
-                if self.min_interval == 1 { "" } else { "s" }
-            )
-        };
-
-        write!(f, "{recurring}{range}: {}", self.title)
+        write!(f, "{}", self.describe(Language::En, Tz::UTC))
     }
 }
 
@@ -298,7 +2447,641 @@ mod tests {
             RoomId::parse("!WBGmhYXnxVfSYOoHua:matrix.com").expect("fails to parse room_id");
         for m in messages {
             debug!("parsing reminder from: {m}");
-            Reminder::try_from_str(m, &room_id).expect("reminder");
+            Reminder::try_from_str(
+                m.trim_start_matches("!reminder"),
+                &room_id,
+                Tz::UTC,
+                &Quiet::default(),
+            )
+            .expect("reminder");
+        }
+
+        // a number alone is an interval, not a time of the day
+        for (m, days) in [
+            ("every 2 day: x", 2),
+            ("every 1 day: x", 1),
+            ("every 12 day: x", 12),
+        ] {
+            let reminder =
+                Reminder::try_from_str(m, &room_id, Tz::UTC, &Quiet::default()).expect("reminder");
+            assert_eq!(reminder.recurrence, None, "{m}");
+            let span = Span {
+                months: 0,
+                seconds: days * 24 * 60 * 60,
+            };
+            assert_eq!(reminder.min_span, Some(span), "{m}");
+        }
+    }
+
+    #[test_log]
+    pub fn calendar_reminder_from_str() {
+        let room_id =
+            RoomId::parse("!WBGmhYXnxVfSYOoHua:matrix.com").expect("fails to parse room_id");
+        let text = normalize_reminder(Language::De, " montags und mittwochs um 9 Uhr: Sport");
+        let reminder =
+            Reminder::try_from_str(&text, &room_id, Tz::Europe__Berlin, &Quiet::default())
+                .expect(&text);
+
+        assert_eq!(
+            reminder.recurrence.map(|r| r.to_string()).as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO,WE;BYHOUR=9;BYMINUTE=0")
+        );
+        assert_eq!(reminder.timezone, Some(Tz::Europe__Berlin));
+        assert!(reminder.recurring && reminder.next_send_at.is_some());
+    }
+
+    #[test_log]
+    pub fn instant_from_str() {
+        let now = "2026-10-18T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let cases = [
+            ("at 17:30", "2026-10-18T17:30:00Z"),
+            ("at 8:00", "2026-10-19T08:00:00Z"),
+            ("at 12", "2026-10-19T12:00:00Z"),
+            ("tomorrow 8am", "2026-10-19T08:00:00Z"),
+            ("tomorrow at 8 pm", "2026-10-19T20:00:00Z"),
+            ("today 12:30pm", "2026-10-18T12:30:00Z"),
+            ("on 2026-12-24 18:00", "2026-12-24T18:00:00Z"),
+            ("on 24.12.2026", "2026-12-24T09:00:00Z"),
+            ("at 18:00 on 2026-12-24", "2026-12-24T18:00:00Z"),
+        ];
+        for (spec, expected) in cases {
+            assert_eq!(
+                parse_instant(spec, now, Tz::UTC).expect(spec).to_rfc3339(),
+                expected.parse::<DateTime<Utc>>().unwrap().to_rfc3339(),
+                "{spec}"
+            );
+        }
+
+        for spec in ["at", "at 25:00", "at 13pm", "at noon", "on 2026-13-01"] {
+            assert!(parse_instant(spec, now, Tz::UTC).is_err(), "{spec}");
+        }
+
+        let berlin = [
+            // CEST, UTC+2
+            ("at 17:30", "2026-10-18T15:30:00Z"),
+            // CET, UTC+1
+            ("on 2026-12-24 18:00", "2026-12-24T17:00:00Z"),
+            // skipped when clocks jump from 2:00 to 3:00, so it's 3:30 CEST
+            ("on 2027-03-28 2:30", "2027-03-28T01:30:00Z"),
+            // happens twice when clocks go back from 3:00 to 2:00, the first one is CEST
+            ("on 2026-10-25 2:30", "2026-10-25T00:30:00Z"),
+        ];
+        for (spec, expected) in berlin {
+            assert_eq!(
+                parse_instant(spec, now, Tz::Europe__Berlin)
+                    .expect(spec)
+                    .to_rfc3339(),
+                expected.parse::<DateTime<Utc>>().unwrap().to_rfc3339(),
+                "{spec}"
+            );
+        }
+
+        assert_eq!(
+            split_title(" at 17:30: Standup: room 2"),
+            Some((" at 17:30", " Standup: room 2"))
+        );
+    }
+
+    #[test]
+    pub fn snooze_from_str() {
+        let minutes = |m: u64| Span {
+            months: 0,
+            seconds: m * 60,
+        };
+
+        assert_eq!(parse_snooze("snooze", Language::En), Some(minutes(15)));
+        assert_eq!(parse_snooze(" Snooze 1h ", Language::En), Some(minutes(60)));
+        assert_eq!(
+            parse_snooze("snooze 1h30m", Language::En),
+            Some(minutes(90))
+        );
+        assert_eq!(
+            parse_snooze("schlummern 5 min", Language::De),
+            Some(minutes(5))
+        );
+        assert_eq!(parse_snooze("snooze 10m", Language::De), Some(minutes(10)));
+
+        for text in [
+            "snoozed",
+            "snooze every 1h",
+            "snooze 1-2h",
+            "snooze soon",
+            "ok",
+        ] {
+            assert_eq!(parse_snooze(text, Language::En), None, "{text}");
+        }
+    }
+
+    #[test]
+    pub fn mentions_from_str() {
+        assert_eq!(
+            split_mentions("@alice  @bob:example.org 2h: Water the plants"),
+            (
+                vec!["@alice".to_string(), "@bob:example.org".to_string()],
+                "2h: Water the plants"
+            )
+        );
+        assert_eq!(
+            split_mentions("2h: Ask @alice"),
+            (Vec::new(), "2h: Ask @alice")
+        );
+
+        let sender = UserId::parse("@carol:matrix.org").unwrap();
+        assert_eq!(
+            resolve_mention("@alice", &sender).map(|u| u.to_string()),
+            Some("@alice:matrix.org".to_string())
+        );
+        assert_eq!(
+            resolve_mention("@bob:example.org", &sender).map(|u| u.to_string()),
+            Some("@bob:example.org".to_string())
+        );
+        assert_eq!(resolve_mention("@alice:", &sender), None);
+    }
+
+    #[test]
+    pub fn edit_from_str() {
+        let kfz = Target("kfz".to_string());
+        let cases = [
+            (
+                "kfz every 3h: Drink water",
+                (kfz.clone(), Edit::Full("every 3h: Drink water".to_string())),
+            ),
+            (
+                "#KFZ every 3h",
+                (kfz.clone(), Edit::Schedule("every 3h".to_string())),
+            ),
+            (
+                "kfz title: at 17:30",
+                (kfz.clone(), Edit::Title("at 17:30".to_string())),
+            ),
+            (
+                "kfz Titel: Wasser",
+                (kfz.clone(), Edit::Title("Wasser".to_string())),
+            ),
+            (
+                "kfz alle 2 Stunden",
+                (kfz, Edit::Schedule("every 2 hours".to_string())),
+            ),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(Edit::parse(text, Language::De), Some(expected), "{text}");
+        }
+
+        for text in ["", "kfz", "3 every 3h", "every 3h: Drink water"] {
+            assert_eq!(Edit::parse(text, Language::En), None, "{text}");
+        }
+    }
+
+    #[test]
+    pub fn short_ids() {
+        for _ in 0..100 {
+            assert!(is_short_id(&new_short_id()));
+        }
+        assert_eq!(
+            split_target(" #Abc until tomorrow"),
+            Some((Target("abc".to_string()), "until tomorrow"))
+        );
+        // look-alikes of digits aren't used, numbers in the list aren't taken
+        for text in ["", "lol", "ab", "abcd", "a1c", "3", "#3 until tomorrow"] {
+            assert_eq!(split_target(text), None, "{text}");
+        }
+        assert_eq!(
+            target_error("3", Language::En, "reminder.which"),
+            tr(Language::En, "reminder.by_id")
+        );
+        assert_eq!(
+            target_error("lol", Language::En, "reminder.which"),
+            tr(Language::En, "reminder.which")
+        );
+    }
+
+    #[test]
+    pub fn edited_reminder() {
+        let room_id = RoomId::parse("!abcdef:example.org").unwrap();
+        let mut reminder = Reminder::try_from_str(
+            "every 2h: Drink water",
+            &room_id,
+            Tz::UTC,
+            &Quiet::default(),
+        )
+        .unwrap();
+        reminder.creator = Some("@alice:example.org".to_string());
+
+        let edited = reminder
+            .edited(
+                &Edit::Title("Drink tea".to_string()),
+                Tz::UTC,
+                &Quiet::default(),
+            )
+            .unwrap();
+        assert_eq!(edited.title, "Drink tea");
+        assert_eq!(edited.next_send_at, reminder.next_send_at);
+
+        let edited = reminder
+            .edited(
+                &Edit::Schedule("every 3h".to_string()),
+                Tz::UTC,
+                &Quiet::default(),
+            )
+            .unwrap();
+        assert_eq!(edited.id, reminder.id);
+        assert_eq!(edited.creator, reminder.creator);
+        assert_eq!(edited.title, "Drink water");
+        assert_eq!(
+            edited.min_span,
+            Some(Span {
+                months: 0,
+                seconds: 3 * 60 * 60
+            })
+        );
+        assert!(edited.next_send_at > reminder.next_send_at);
+
+        let edited = reminder
+            .edited(
+                &Edit::Full("every monday: Standup".to_string()),
+                Tz::UTC,
+                &Quiet::default(),
+            )
+            .unwrap();
+        assert_eq!(edited.min_span, None);
+        assert!(edited.recurrence.is_some());
+        assert_eq!(edited.title, "Standup");
+
+        assert!(reminder
+            .edited(&Edit::Title(String::new()), Tz::UTC, &Quiet::default())
+            .is_err());
+        assert!(reminder
+            .edited(
+                &Edit::Schedule("whenever".to_string()),
+                Tz::UTC,
+                &Quiet::default()
+            )
+            .is_err());
+    }
+
+    #[test]
+    pub fn pause_until_from_str() {
+        let now = "2026-10-18T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let until = |spec: &str, tz: Tz| parse_until(spec, now, tz).map(|t| t.to_rfc3339()).ok();
+
+        assert_eq!(
+            until("until 2027-01-07", Tz::UTC),
+            Some("2027-01-07T00:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            until("until 07.01.2027 8:00", Tz::Europe__Berlin),
+            Some("2027-01-07T07:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            until(&normalize_reminder(Language::De, "bis morgen"), Tz::UTC),
+            Some("2026-10-19T00:00:00+00:00".to_string())
+        );
+        assert_eq!(until("until 2026-10-01", Tz::UTC), None);
+        assert_eq!(until("until whenever", Tz::UTC), None);
+    }
+
+    #[test]
+    pub fn resumed_next_send() {
+        let room_id = RoomId::parse("!abcdef:example.org").unwrap();
+        let now = Utc::now();
+        let later = now + chrono::Duration::days(30);
+
+        // missed recurring reminders continue after the pause
+        let mut reminder = Reminder::try_from_str(
+            "every 2h: Drink water",
+            &room_id,
+            Tz::UTC,
+            &Quiet::default(),
+        )
+        .unwrap();
+        reminder.next_send_at = Some(now - chrono::Duration::days(3));
+        let next = reminder.resumed_next(now, &Quiet::default()).unwrap();
+        assert!(next > now && next <= now + chrono::Duration::hours(2));
+
+        // missed one-off reminders are sent once
+        let mut reminder =
+            Reminder::try_from_str("on 2026-12-24: Gifts", &room_id, Tz::UTC, &Quiet::default())
+                .unwrap();
+        reminder.next_send_at = Some(now - chrono::Duration::days(3));
+        assert_eq!(reminder.resumed_next(now, &Quiet::default()), Some(now));
+
+        // short pauses don't change the schedule
+        reminder.next_send_at = Some(later);
+        assert_eq!(reminder.resumed_next(now, &Quiet::default()), Some(later));
+    }
+
+    #[test]
+    pub fn schedule_order() {
+        let room_id = RoomId::parse("!abcdef:example.org").unwrap();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let reminder = |next: &str| {
+            let mut r = Reminder::try_from_str(
+                "every 1h: Drink water",
+                &room_id,
+                Tz::UTC,
+                &Quiet::default(),
+            )
+            .unwrap();
+            r.next_send_at = Some(at(next));
+            r
+        };
+
+        let mut schedule = Schedule::default();
+        let late = reminder("2026-10-18T14:00:00Z");
+        let mut early = reminder("2026-10-18T12:00:00Z");
+        let deleted = reminder("2026-10-18T11:00:00Z");
+        for r in [&late, &early, &deleted] {
+            schedule.push(r);
+        }
+        schedule.remove(&deleted.id);
+        assert_eq!(schedule.next_wake(), Some(at("2026-10-18T12:00:00Z")));
+
+        // an edit moves the reminder, its old time is skipped
+        early.next_send_at = Some(at("2026-10-18T13:00:00Z"));
+        schedule.push(&early);
+        assert_eq!(
+            schedule.pop_due(at("2026-10-18T12:30:00Z")),
+            Vec::<Thing>::new()
+        );
+        assert_eq!(schedule.next_wake(), Some(at("2026-10-18T13:00:00Z")));
+
+        // paused reminders wake when their pause ends
+        let mut paused = reminder("2026-10-18T12:00:00Z");
+        paused.paused = true;
+        paused.paused_until = Some(at("2026-10-18T13:30:00Z"));
+        schedule.push(&paused);
+
+        assert_eq!(
+            schedule.pop_due(at("2026-10-18T14:00:00Z")),
+            vec![early.id.clone(), paused.id.clone(), late.id.clone()]
+        );
+        assert_eq!(schedule.next_wake(), None);
+        assert_eq!(schedule.len(), 0);
+    }
+
+    #[test]
+    pub fn end_conditions() {
+        let room_id = RoomId::parse("!abcdef:example.org").unwrap();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let now = at("2026-10-18T12:00:00Z");
+
+        let end = |at, times| End { at, times };
+        assert_eq!(
+            split_end("every 1d until 2026-12-01", now, Tz::UTC),
+            Some(("every 1d", end(Some(at("2026-12-01T23:59:59Z")), None)))
+        );
+        assert_eq!(
+            split_end("every 2h 5 times", now, Tz::UTC),
+            Some(("every 2h", end(None, Some(5))))
+        );
+        assert_eq!(
+            split_end(
+                "every monday at 9:00 3 times to 2026-12-24 12:00",
+                now,
+                Tz::UTC
+            ),
+            Some((
+                "every monday at 9:00",
+                end(Some(at("2026-12-24T12:00:00Z")), Some(3))
+            ))
+        );
+        // ranges aren't ends
+        for spec in ["every 10 to 20 days", "every 1 until 2h", "every 0 times"] {
+            assert_eq!(split_end(spec, now, Tz::UTC), None, "{spec}");
+        }
+
+        let reminder = Reminder::try_from_str(
+            "every 2h 5 times: Stretch",
+            &room_id,
+            Tz::UTC,
+            &Quiet::default(),
+        )
+        .unwrap();
+        assert_eq!(reminder.remaining, Some(5));
+        assert_eq!(reminder.to_string(), "every 2 hours, 5 more times: Stretch");
+        let text = normalize_reminder(Language::De, "jeden Tag bis 01.12.2099: Tabletten");
+        let reminder = Reminder::try_from_str(&text, &room_id, Tz::UTC, &Quiet::default()).unwrap();
+        assert_eq!(reminder.ends_at, Some(at("2099-12-01T23:59:59Z")));
+        assert_eq!(
+            reminder.describe(Language::De, Tz::UTC),
+            "jeden Tag um 09:00, bis 01.12.2099: Tabletten"
+        );
+        for text in ["2h 5 times: Stretch", "every 2h until 2020-01-01: Stretch"] {
+            assert!(
+                Reminder::try_from_str(text, &room_id, Tz::UTC, &Quiet::default()).is_err(),
+                "{text}"
+            );
+        }
+
+        // every time counts down, the last one ends the reminder
+        let mut reminder = Reminder::try_from_str(
+            "every 1h 2 times: Stretch",
+            &room_id,
+            Tz::UTC,
+            &Quiet::default(),
+        )
+        .unwrap();
+        let (next, remaining) = reminder.following(now, &Quiet::default());
+        assert_eq!(
+            (next, remaining),
+            (Some(at("2026-10-18T13:00:00Z")), Some(1))
+        );
+        reminder.remaining = remaining;
+        assert_eq!(reminder.to_string(), "every 1 hour, once more: Stretch");
+        assert_eq!(reminder.following(now, &Quiet::default()), (None, Some(0)));
+
+        reminder.remaining = None;
+        reminder.ends_at = Some(at("2026-10-18T12:30:00Z"));
+        assert_eq!(reminder.following(now, &Quiet::default()), (None, None));
+    }
+
+    #[test]
+    pub fn reminder_hours() {
+        let room_id = RoomId::parse("!abcdef:example.org").unwrap();
+        let quiet = Quiet {
+            hours: Some(Hours::parse("22:00-7:00").unwrap()),
+            tz: Tz::Europe__Berlin,
+        };
+
+        let reminder = Reminder::try_from_str(
+            "every 1-3d between 9:00 and 18:00 5 times: Gym",
+            &room_id,
+            Tz::UTC,
+            &quiet,
+        )
+        .unwrap();
+        assert_eq!(reminder.hours, Hours::parse("9:00-18:00"));
+        assert_eq!(reminder.remaining, Some(5));
+        assert!(reminder
+            .to_string()
+            .ends_with("between 09:00 and 18:00, 5 more times: Gym"));
+        // on the room's clock
+        let next = reminder
+            .next_send_at
+            .unwrap()
+            .with_timezone(&Tz::Europe__Berlin);
+        assert!(reminder.hours.unwrap().contains(next.time()), "{next}");
+
+        let text = normalize_reminder(Language::De, "alle 1-3 Tage zwischen 9 und 18 Uhr: Sport");
+        let reminder = Reminder::try_from_str(&text, &room_id, Tz::UTC, &quiet).unwrap();
+        assert_eq!(reminder.hours, Hours::parse("9:00-18:00"));
+
+        // fixed times are kept
+        for text in [
+            "every 2h between 9 and 18: Water",
+            "at 17:30 between 9 and 18: Standup",
+        ] {
+            assert!(
+                Reminder::try_from_str(text, &room_id, Tz::UTC, &quiet).is_err(),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    pub fn failed_delivery() {
+        let room_id = RoomId::parse("!abcdef:example.org").unwrap();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let now = at("2026-10-18T12:00:00Z");
+        let mut reminder = Reminder::try_from_str(
+            "every 1h: Drink water",
+            &room_id,
+            Tz::UTC,
+            &Quiet::default(),
+        )
+        .unwrap();
+        reminder.next_send_at = Some(now);
+        reminder.delivery.txn_id = Some("m1760788800000.0".to_string());
+
+        // retries keep the transaction id and wait longer each time
+        let waits: Vec<_> = (0..MAX_ATTEMPTS)
+            .scan(reminder.delivery.clone(), |delivery, _| {
+                *delivery = delivery.retried(now)?;
+                assert_eq!(delivery.txn_id, reminder.delivery.txn_id);
+                Some((delivery.retry_at.unwrap() - now).num_seconds())
+            })
+            .collect();
+        assert_eq!(waits, [30, 60, 120, 240, 480, 960, 1920, 3600, 3600]);
+
+        // the scheduler wakes the reminder for its retry
+        reminder.delivery = reminder.delivery.retried(now).unwrap();
+        assert_eq!(reminder.wake_at(), Some(at("2026-10-18T12:00:30Z")));
+        reminder.delivery.retry_at = None;
+        assert_eq!(reminder.wake_at(), Some(now));
+    }
+
+    #[test]
+    pub fn chained_commands() {
+        let room_id = RoomId::parse("!abcdef:example.org").unwrap();
+        let mut reminder = Reminder::try_from_str(
+            "1m: !botto disable reminder",
+            &room_id,
+            Tz::UTC,
+            &Quiet::default(),
+        )
+        .unwrap();
+        // reminders from before creators were recorded don't run their command
+        assert_eq!(reminder.chain_sender(), None);
+
+        // the command runs as its creator, so a member without moderator rights is refused like when typing it
+        reminder.creator = Some("@mallory:example.org".to_string());
+        let sender = reminder.chain_sender().unwrap();
+        assert_eq!(sender, "@mallory:example.org");
+        assert!(reminder.created_by(&sender));
+        assert!(!settings::has_settings_power(0));
+        assert!(settings::has_settings_power(settings::SETTINGS_POWER_LEVEL));
+    }
+
+    #[test]
+    pub fn ics_events() {
+        use chrono::NaiveDateTime;
+
+        let room_id = RoomId::parse("!abcdef:example.org").unwrap();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let now = at("2026-10-18T12:00:00Z");
+        let berlin = Tz::Europe__Berlin;
+        let reminder =
+            |text| Reminder::try_from_str(text, &room_id, berlin, &Quiet::default()).unwrap();
+
+        let standup = reminder("every monday at 9:00 3 times: Standup")
+            .to_event(Language::En)
+            .unwrap();
+        assert_eq!(
+            standup.rrule.as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0;COUNT=3")
+        );
+        assert_eq!(standup.start.tz(), Some(berlin));
+        let haircut = reminder("every 42 days: Get a haircut")
+            .to_event(Language::En)
+            .unwrap();
+        assert_eq!(haircut.rrule.as_deref(), Some("FREQ=WEEKLY;INTERVAL=6"));
+        // random times have no rule, the schedule is described instead
+        let gym = reminder("every 1-3d: Go to the gym")
+            .to_event(Language::En)
+            .unwrap();
+        assert_eq!(gym.rrule, None);
+        assert_eq!(
+            gym.description.as_deref(),
+            Some("every 1 - 3 days: Go to the gym")
+        );
+
+        let event = |start, rrule: Option<&str>| ics::Event {
+            uid: "a@example.org".to_string(),
+            summary: "Standup".to_string(),
+            description: None,
+            start,
+            rrule: rrule.map(str::to_string),
+        };
+        let local = |s: &str| {
+            ics::Start::Local(
+                NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap(),
+                Some(berlin),
+            )
+        };
+        let import = |event| Reminder::from_event(&event, &room_id, Tz::UTC, now);
+
+        // the missing day and time are the ones of the first event, on monday 2026-10-05
+        let weekly = import(event(
+            local("2026-10-05 09:00"),
+            Some("FREQ=WEEKLY;COUNT=4"),
+        ))
+        .unwrap();
+        assert_eq!(
+            weekly
+                .recurrence
+                .as_ref()
+                .map(ToString::to_string)
+                .as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0")
+        );
+        assert_eq!(weekly.next_send_at, Some(at("2026-10-19T07:00:00Z")));
+        assert_eq!(weekly.remaining, Some(2));
+        let hourly = import(event(
+            ics::Start::Utc(at("2026-10-18T09:30:00Z")),
+            Some("FREQ=HOURLY;INTERVAL=2;UNTIL=20261231"),
+        ))
+        .unwrap();
+        assert_eq!(hourly.next_send_at, Some(at("2026-10-18T13:30:00Z")));
+        assert_eq!(hourly.ends_at, Some(at("2026-12-31T23:59:59Z")));
+        let once = import(event(ics::Start::Date("2026-12-24".parse().unwrap()), None)).unwrap();
+        assert_eq!(once.send_at, Some(at("2026-12-24T09:00:00Z")));
+
+        for (start, rrule) in [
+            (local("2026-10-01 09:00"), None),
+            (local("2026-10-05 09:00"), Some("FREQ=WEEKLY;COUNT=2")),
+            (local("2026-10-05 09:00"), Some("FREQ=MONTHLY;BYDAY=1MO")),
+            (local("2026-10-05 09:00"), Some("FREQ=SECONDLY")),
+        ] {
+            assert!(import(event(start, rrule)).is_err(), "{start} {rrule:?}");
         }
+
+        // a round trip keeps the schedule
+        let round_trip = import(standup).unwrap();
+        assert_eq!(
+            round_trip.recurrence,
+            reminder("every monday at 9:00: x").recurrence
+        );
+        assert_eq!(round_trip.timezone, Some(berlin));
+        assert_eq!(round_trip.remaining, Some(3));
     }
 }
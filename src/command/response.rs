@@ -0,0 +1,113 @@
+use crate::prelude::*;
+use matrix_sdk::ruma::{EventId, OwnedEventId};
+
+/// A command's answer, with a plain text body for clients that don't render HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub body: String,
+    pub html: String,
+}
+
+impl Response {
+    pub fn new(body: impl Into<String>, html: impl Into<String>) -> Self {
+        Self {
+            body: body.into(),
+            html: html.into(),
+        }
+    }
+
+    /// A response without formatting, the HTML body is the escaped plain body.
+    pub fn plain(body: impl Into<String>) -> Self {
+        let body = body.into();
+        let html = escape(&body);
+
+        Self { body, html }
+    }
+
+    pub fn into_content(self) -> RoomMessageEventContent {
+        RoomMessageEventContent::text_html(self.body, self.html)
+    }
+}
+
+impl From<String> for Response {
+    fn from(body: String) -> Self {
+        Self::plain(body)
+    }
+}
+
+impl From<&str> for Response {
+    fn from(body: &str) -> Self {
+        Self::plain(body)
+    }
+}
+
+/// Looks up the event the bot answered the given command event in the room with.
+pub async fn find_sent(
+    room_id: &RoomId,
+    request_event_id: &EventId,
+    db: &Surreal<Any>,
+) -> Result<Option<OwnedEventId>> {
+    let sent: Option<String> = db
+        .query("select value response_event_id from type::thing('response', $request_event_id) where room_id = $room_id")
+        .bind(("room_id", room_id.to_string()))
+        .bind(("request_event_id", request_event_id.to_string()))
+        .await?
+        .take(0)
+        .context("fails to select response")?;
+
+    sent.map(|id| EventId::parse(id).context("fails to parse response event id"))
+        .transpose()
+}
+
+/// Remembers which event answered which command event, so edits of the command can edit the answer.
+pub async fn save_sent(
+    room_id: &RoomId,
+    request_event_id: &EventId,
+    response_event_id: &EventId,
+    db: &Surreal<Any>,
+) -> Result<()> {
+    db.query("create type::thing('response', $request_event_id) set room_id = $room_id, response_event_id = $response_event_id")
+        .bind(("room_id", room_id.to_string()))
+        .bind(("request_event_id", request_event_id.to_string()))
+        .bind(("response_event_id", response_event_id.to_string()))
+        .await?
+        .check()
+        .context("fails to create response")?;
+
+    Ok(())
+}
+
+/// Escapes the text and styles it as code, e.g. for commands.
+pub fn code(text: &str) -> String {
+    format!("<code>{}</code>", escape(text))
+}
+
+/// Escapes text for use in an HTML body and keeps its line breaks.
+pub fn escape(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&#39;"),
+            '\n' => res.push_str("<br>"),
+            c => res.push(c),
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn plain_response() {
+        let resp = Response::plain("<b>1d20</b> & co\n🟰 3");
+        assert_eq!(resp.body, "<b>1d20</b> & co\n🟰 3");
+        assert_eq!(resp.html, "&lt;b&gt;1d20&lt;/b&gt; &amp; co<br>🟰 3");
+    }
+}
@@ -1,8 +1,43 @@
-use crate::prelude::*;
+use crate::{
+    command::{async_trait, response::escape, Command, Ctx, Response},
+    locale::Language,
+    prelude::*,
+};
 use rand::Rng;
 use regex::Regex;
 
-pub fn dice(text: &str) -> String {
+pub struct Roll;
+
+#[async_trait]
+impl Command for Roll {
+    type Args = String;
+
+    fn name(&self) -> &'static str {
+        "roll"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["r"]
+    }
+
+    fn description(&self) -> &'static str {
+        "🎲 - dice roll"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["r d6", "r 2d8", "r 2d6 - 1d4 + 3"]
+    }
+
+    fn parse(&self, args: &str, _lang: Language) -> Result<Self::Args, String> {
+        Ok(args.to_lowercase())
+    }
+
+    async fn run(&self, _ctx: &Ctx<'_>, args: Self::Args) -> Result<Option<Response>> {
+        Ok(Some(dice(&args)))
+    }
+}
+
+pub fn dice(text: &str) -> Response {
     let re =
         Regex::new(r"(([-+]{0,1})([ ]*)([0-9]{0,10})([ dD]*)([0-9]+))").expect("regex is valid");
 
@@ -79,9 +114,12 @@ pub fn dice(text: &str) -> String {
     info!("🎲 rolling: {explanation} => {sum}");
 
     if dice_count > 1 {
-        format!("{explanation}\n\n🟰 {sum}")
+        Response::new(
+            format!("{explanation}\n\n🟰 {sum}"),
+            format!("{}<br><br>🟰 <strong>{sum}</strong>", escape(&explanation)),
+        )
     } else {
-        format!("{sum}")
+        Response::new(format!("{sum}"), format!("<strong>{sum}</strong>"))
     }
 }
 
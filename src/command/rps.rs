@@ -1,46 +1,88 @@
-use crate::prelude::*;
+use crate::{
+    command::{async_trait, Command, Ctx, Response},
+    locale::{tr, Language},
+    prelude::*,
+};
 use rand::seq::SliceRandom;
 use std::cmp::Ordering;
 
-pub fn play(text: String) -> String {
-    match Rps::try_from_string(text) {
-        Ok(user_choice) => {
-            let options = [Rps::Rock, Rps::Paper, Rps::Scissors];
-            let bot_choice = options.choose(&mut rand::thread_rng()).unwrap_or_else(|| {
-                warn!("fails to choose rps option");
-                &Rps::Rock
-            });
-
-            let res = user_choice.defeats(bot_choice);
-
-            format!(
-                "{user_choice} 💥 {bot_choice}\n{}",
-                match res {
-                    Ordering::Less => "You lose.",
-                    Ordering::Equal => "It's a tie.",
-                    Ordering::Greater => "You win!",
-                }
-            )
-        }
-        Err(err) => err,
+pub struct RockPaperScissors;
+
+#[async_trait]
+impl Command for RockPaperScissors {
+    type Args = Rps;
+
+    fn name(&self) -> &'static str {
+        "rps"
+    }
+
+    fn description(&self) -> &'static str {
+        "🪨 - rock paper scissors"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &["rps rock", "rps paper", "rps scissors"]
+    }
+
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String> {
+        Rps::try_from_str(args, lang)
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, args: Self::Args) -> Result<Option<Response>> {
+        Ok(Some(play(args, ctx.lang).into()))
     }
 }
 
+pub fn play(user_choice: Rps, lang: Language) -> String {
+    let options = [Rps::Rock, Rps::Paper, Rps::Scissors];
+    let bot_choice = options.choose(&mut rand::thread_rng()).unwrap_or_else(|| {
+        warn!("fails to choose rps option");
+        &Rps::Rock
+    });
+
+    let res = user_choice.defeats(bot_choice);
+
+    format!(
+        "{user_choice} 💥 {bot_choice}\n{}",
+        tr(
+            lang,
+            match res {
+                Ordering::Less => "rps.lose",
+                Ordering::Equal => "rps.tie",
+                Ordering::Greater => "rps.win",
+            }
+        )
+    )
+}
+
 #[derive(PartialEq, Eq)]
-enum Rps {
+pub enum Rps {
     Rock,
     Paper,
     Scissors,
 }
 
 impl Rps {
-    fn try_from_string(text: String) -> Result<Self, String> {
-        match text.to_lowercase().trim() {
-            "rock" => Ok(Self::Rock),
-            "paper" => Ok(Self::Paper),
-            "scissors" => Ok(Self::Scissors),
-            _ => Err("Invalid choice. Please choose 'rock', 'paper', or 'scissors'.".to_string()),
-        }
+    const ALL: [Self; 3] = [Self::Rock, Self::Paper, Self::Scissors];
+
+    /// Accepts the English choices and the ones of the room's language.
+    fn try_from_str(text: &str, lang: Language) -> Result<Self, String> {
+        let text = text.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|rps| text == rps.name(Language::En) || text == rps.name(lang))
+            .ok_or_else(|| tr(lang, "rps.invalid").to_string())
+    }
+
+    fn name(&self, lang: Language) -> &'static str {
+        tr(
+            lang,
+            match self {
+                Self::Rock => "rps.rock",
+                Self::Paper => "rps.paper",
+                Self::Scissors => "rps.scissors",
+            },
+        )
     }
 
     fn defeats(&self, other: &Self) -> Ordering {
@@ -0,0 +1,262 @@
+use crate::{
+    command::recurrence::localize,
+    locale::{tr, Language},
+    prelude::*,
+};
+use chrono::Months;
+use chrono_tz::Tz;
+use regex::Regex;
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Length of a reminder's interval like `1h30m` or `2 weeks 3 days`.
+/// Months and years are calendar months, everything else is an exact number of seconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Span {
+    pub months: u32,
+    pub seconds: u64,
+}
+
+/// Unit of a number in a span, `None` for bare numbers like the `1` in `1-3 days`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Seconds(u64),
+    Months(u32),
+}
+
+impl Span {
+    /// Parses the schedule of relative reminders, e.g. `10m`, `every 1h30m`, `1-3 days` or `every 2 to 3 weeks`.
+    /// Returns whether it's recurring, the span and the longest span of random ones.
+    pub fn parse_range(text: &str) -> Option<(bool, Self, Option<Self>)> {
+        let text = text.trim().to_lowercase();
+        let (recurring, text) = match text.strip_prefix("every") {
+            Some(rest) => (true, rest),
+            None => (false, text.as_str()),
+        };
+
+        let separator = Regex::new(r"-+|–|—|\bto\b|\buntil\b").expect("regex is valid");
+        let parts = separator.splitn(text, 2).collect::<Vec<_>>();
+        let (min, max) = match parts[..] {
+            [min, max] => (tokens(min), tokens(max)),
+            _ => {
+                // `14 g 9m` is a range, the bare number takes the unit of the other
+                let tokens = tokens(text);
+                match tokens.split_first() {
+                    Some((first, rest)) if first.1.is_none() && !rest.is_empty() => {
+                        (vec![*first], rest.to_vec())
+                    }
+                    _ => (tokens, Vec::new()),
+                }
+            }
+        };
+
+        let shared_unit = max.first().and_then(|(_, unit)| *unit);
+        let min = Self::from_tokens(&min, shared_unit)?;
+        let max = match max.is_empty() {
+            true => None,
+            false => Some(Self::from_tokens(&max, None)?),
+        };
+
+        Some((recurring, min, max.filter(|max| *max > min)))
+    }
+
+    fn from_tokens(tokens: &[(u64, Option<Unit>)], default_unit: Option<Unit>) -> Option<Self> {
+        let mut span = Self::default();
+        for (n, unit) in tokens {
+            match unit.or(default_unit)? {
+                Unit::Seconds(s) => span.seconds = span.seconds.checked_add(n.checked_mul(s)?)?,
+                Unit::Months(m) => {
+                    let months = u32::try_from(*n).ok()?.checked_mul(m)?;
+                    span.months = span.months.checked_add(months)?;
+                }
+            }
+        }
+
+        (span != Self::default()).then_some(span)
+    }
+
+    /// The instant the span after `start`, months are added on the clocks of `tz`.
+    pub fn after(&self, start: DateTime<Utc>, tz: Tz) -> Option<DateTime<Utc>> {
+        let start = match self.months {
+            0 => start,
+            months => {
+                let local = start.with_timezone(&tz).naive_local();
+                localize(tz, local.checked_add_months(Months::new(months))?)
+            }
+        };
+
+        start.checked_add_signed(chrono::Duration::seconds(i64::try_from(self.seconds).ok()?))
+    }
+
+    /// E.g. `1 hour 30 minutes` in the given language.
+    pub fn describe(&self, lang: Language) -> String {
+        let mut parts = Vec::new();
+        let mut push = |n: u64, unit: &str| {
+            if n > 0 {
+                let plural = if n == 1 { "" } else { "s" };
+                parts.push(format!(
+                    "{n} {}",
+                    tr(lang, &format!("reminder.{unit}{plural}"))
+                ));
+            }
+        };
+
+        push(u64::from(self.months / 12), "year");
+        push(u64::from(self.months % 12), "month");
+        if self.seconds > 0 && self.seconds.is_multiple_of(WEEK) {
+            push(self.seconds / WEEK, "week");
+        } else {
+            push(self.seconds / DAY, "day");
+            push(self.seconds % DAY / HOUR, "hour");
+            push(self.seconds % HOUR / MINUTE, "minute");
+            push(self.seconds % MINUTE, "second");
+        }
+
+        parts.join(" ")
+    }
+
+    /// The number of the span's only unit and the unit, e.g. `(3, "day")` for 3 days.
+    fn single_unit(&self) -> Option<(u64, &'static str)> {
+        match (self.months, self.seconds) {
+            (0, 0) => None,
+            (months, 0) if months.is_multiple_of(12) => Some((u64::from(months / 12), "year")),
+            (months, 0) => Some((u64::from(months), "month")),
+            (0, s) => [
+                (WEEK, "week"),
+                (DAY, "day"),
+                (HOUR, "hour"),
+                (MINUTE, "minute"),
+                (1, "second"),
+            ]
+            .into_iter()
+            .find(|(unit, _)| s.is_multiple_of(*unit))
+            .map(|(unit, name)| (s / unit, name)),
+            _ => None,
+        }
+    }
+}
+
+/// E.g. `1 - 3 days` if both spans are in the same unit, `1 hour 30 minutes - 2 hours` otherwise.
+pub fn describe_range(min: &Span, max: &Span, lang: Language) -> String {
+    match (min.single_unit(), max.single_unit()) {
+        (Some((min, min_unit)), Some((max, max_unit))) if min_unit == max_unit => {
+            format!(
+                "{min} - {max} {}",
+                tr(lang, &format!("reminder.{max_unit}s"))
+            )
+        }
+        _ => format!("{} - {}", min.describe(lang), max.describe(lang)),
+    }
+}
+
+/// Numbers and their units in e.g. `1h 30 minutes`, unknown words are no unit.
+fn tokens(text: &str) -> Vec<(u64, Option<Unit>)> {
+    let re = Regex::new(r"([0-9]+)\s*([a-z]+)?").expect("regex is valid");
+    re.captures_iter(text)
+        .filter_map(|caps| {
+            let n = caps[1].parse().ok()?;
+            Some((n, caps.get(2).and_then(|u| unit(u.as_str()))))
+        })
+        .collect()
+}
+
+fn unit(word: &str) -> Option<Unit> {
+    let unit = match word {
+        "s" | "sec" | "secs" | "second" | "seconds" => Unit::Seconds(1),
+        "m" | "min" | "mins" | "minute" | "minutes" => Unit::Seconds(MINUTE),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Unit::Seconds(HOUR),
+        "d" | "day" | "days" => Unit::Seconds(DAY),
+        "w" | "wk" | "wks" | "week" | "weeks" => Unit::Seconds(WEEK),
+        "mo" | "month" | "months" => Unit::Months(1),
+        "y" | "yr" | "yrs" | "year" | "years" => Unit::Months(12),
+        _ => return None,
+    };
+
+    Some(unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(months: u32, seconds: u64) -> Span {
+        Span { months, seconds }
+    }
+
+    #[test]
+    pub fn parse_span() {
+        let cases = [
+            ("10 minutes", (false, span(0, 600), None)),
+            ("every 1h30m", (true, span(0, 5400), None)),
+            ("2 weeks 3 days", (false, span(0, 17 * DAY), None)),
+            (
+                "every 1 – 3 day",
+                (true, span(0, DAY), Some(span(0, 3 * DAY))),
+            ),
+            (
+                "every 30 to 60 days",
+                (true, span(0, 30 * DAY), Some(span(0, 60 * DAY))),
+            ),
+            (
+                "1h30m to 2h",
+                (false, span(0, 5400), Some(span(0, 2 * HOUR))),
+            ),
+            ("14 g 9m", (false, span(0, 14 * MINUTE), None)),
+            (
+                "every1 -asfas 📹 3d",
+                (true, span(0, DAY), Some(span(0, 3 * DAY))),
+            ),
+            ("1 month", (false, span(1, 0), None)),
+            ("every 1y 2mo", (true, span(14, 0), None)),
+            ("45 s", (false, span(0, 45), None)),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(Span::parse_range(text), Some(expected), "{text}");
+        }
+
+        for text in ["", "every", "0 minutes", "3 fortnights", "every monday"] {
+            assert_eq!(Span::parse_range(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    pub fn span_after() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+
+        // calendar months, clamped to the end of shorter months
+        assert_eq!(
+            span(1, 0).after(at("2027-01-31T10:00:00Z"), Tz::UTC),
+            Some(at("2027-02-28T10:00:00Z"))
+        );
+        // the same wall clock time in Berlin after the switch to CET
+        assert_eq!(
+            span(1, 0).after(at("2026-10-18T08:00:00Z"), Tz::Europe__Berlin),
+            Some(at("2026-11-18T09:00:00Z"))
+        );
+        assert_eq!(
+            span(0, 5400).after(at("2026-10-18T08:00:00Z"), Tz::Europe__Berlin),
+            Some(at("2026-10-18T09:30:00Z"))
+        );
+    }
+
+    #[test]
+    pub fn describe_span() {
+        assert_eq!(span(0, 5400).describe(Language::En), "1 hour 30 minutes");
+        assert_eq!(span(0, 2 * WEEK).describe(Language::En), "2 weeks");
+        assert_eq!(
+            span(14, DAY).describe(Language::En),
+            "1 year 2 months 1 day"
+        );
+        assert_eq!(
+            describe_range(&span(0, DAY), &span(0, 3 * DAY), Language::En),
+            "1 - 3 days"
+        );
+        assert_eq!(
+            describe_range(&span(0, 5400), &span(0, 2 * HOUR), Language::De),
+            "1 Stunde 30 Minuten - 2 Stunden"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    command::{async_trait, Command, Ctx, Response},
+    locale::{tr, trf, Language},
+    prelude::*,
+    settings::{self, UserSettings},
+};
+use chrono_tz::Tz;
+
+/// `!timezone Europe/Berlin` sets the sender's timezone, `!timezone room Europe/Berlin` the room's.
+pub struct Timezone;
+
+pub enum Request {
+    Show,
+    User(Tz),
+    Room(Tz),
+}
+
+#[async_trait]
+impl Command for Timezone {
+    type Args = Request;
+
+    fn name(&self) -> &'static str {
+        "timezone"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["tz"]
+    }
+
+    fn reruns_on_edit(&self) -> bool {
+        false
+    }
+
+    fn description(&self) -> &'static str {
+        "🌍 - timezone for reminders, yours or the room's"
+    }
+
+    fn usage(&self) -> &'static [&'static str] {
+        &[
+            "timezone",
+            "timezone Europe/Berlin",
+            "timezone room America/New_York",
+        ]
+    }
+
+    fn parse(&self, args: &str, lang: Language) -> Result<Self::Args, String> {
+        let args = args.trim();
+        let (room, name) = match args.split_once(char::is_whitespace) {
+            Some((scope, name)) if scope.eq_ignore_ascii_case("room") => (true, name.trim()),
+            _ => (false, args),
+        };
+        if name.is_empty() {
+            return Ok(Request::Show);
+        }
+
+        let tz =
+            parse_tz(name).ok_or_else(|| trf(lang, "timezone.invalid", &[("timezone", &name)]))?;
+
+        Ok(if room {
+            Request::Room(tz)
+        } else {
+            Request::User(tz)
+        })
+    }
+
+    async fn run(&self, ctx: &Ctx<'_>, args: Self::Args) -> Result<Option<Response>> {
+        let resp = match args {
+            Request::Show => {
+                let user = UserSettings::load(ctx.sender, ctx.db).await?;
+                trf(
+                    ctx.lang,
+                    "timezone.current",
+                    &[
+                        (
+                            "user",
+                            &user
+                                .timezone
+                                .map(|tz| tz.name())
+                                .unwrap_or(tr(ctx.lang, "timezone.unset")),
+                        ),
+                        ("room", &ctx.settings.timezone.unwrap_or(Tz::UTC).name()),
+                        ("prefix", &ctx.prefix),
+                    ],
+                )
+            }
+            Request::User(tz) => {
+                let mut user = UserSettings::load(ctx.sender, ctx.db).await?;
+                user.timezone = Some(tz);
+                user.save(ctx.sender, ctx.db).await?;
+
+                trf(ctx.lang, "timezone.user_set", &[("timezone", &tz.name())])
+            }
+            Request::Room(tz) => {
+                let room_id = ctx.room.room_id();
+                if !settings::may_change(ctx.room, ctx.sender).await? {
+                    info!(
+                        "⚙️ {} may not change settings of room {room_id}",
+                        ctx.sender
+                    );
+                    return Ok(Some(tr(ctx.lang, "settings.forbidden").into()));
+                }
+
+                let mut room_settings = ctx.settings.clone();
+                room_settings.timezone = Some(tz);
+                room_settings.save(room_id, ctx.db).await?;
+
+                trf(ctx.lang, "timezone.room_set", &[("timezone", &tz.name())])
+            }
+        };
+
+        Ok(Some(resp.into()))
+    }
+}
+
+/// Parses IANA names like `Europe/Berlin` regardless of case.
+fn parse_tz(name: &str) -> Option<Tz> {
+    name.parse().ok().or_else(|| {
+        chrono_tz::TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().eq_ignore_ascii_case(name))
+            .copied()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_timezone() {
+        assert_eq!(parse_tz("Europe/Berlin"), Some(Tz::Europe__Berlin));
+        assert_eq!(parse_tz("europe/berlin"), Some(Tz::Europe__Berlin));
+        assert_eq!(parse_tz("UTC"), Some(Tz::UTC));
+        assert_eq!(parse_tz("Mars/Olympus_Mons"), None);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{command::limit::Rate, locale::Language, prelude::*, settings};
+use matrix_sdk::ruma::{OwnedUserId, UserId};
+use std::collections::HashMap;
+
+/// Deployment wide settings, read from the environment on startup.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Prefix for commands in rooms that don't set their own.
+    pub prefix: String,
+    /// Language of rooms that don't set their own.
+    pub language: Language,
+    /// Users whose messages are never treated as commands, e.g. other bots.
+    pub ignored_users: Vec<OwnedUserId>,
+    /// Rates per command name for each sender, `*` applies to all commands without their own.
+    pub user_rate_limits: HashMap<String, Rate>,
+    /// Rates per command name for each room, `*` applies to all commands without their own.
+    pub room_rate_limits: HashMap<String, Rate>,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self> {
+        let prefix = env::var("COMMAND_PREFIX").unwrap_or_else(|_| "!".to_string());
+        if !settings::is_valid_prefix(&prefix) {
+            bail!(
+                "COMMAND_PREFIX must be 1 to {} characters without whitespace",
+                settings::MAX_PREFIX_LEN
+            );
+        }
+
+        let language = match env::var("LANGUAGE") {
+            Ok(language) => language.parse().context("LANGUAGE must be en or de")?,
+            Err(_) => Language::default(),
+        };
+
+        let ignored_users = env::var("IGNORED_USERS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                UserId::parse(s)
+                    .with_context(|| format!("IGNORED_USERS contains invalid user id {s}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            prefix,
+            language,
+            ignored_users,
+            user_rate_limits: rate_limits_from_env("USER_RATE_LIMITS")?,
+            room_rate_limits: rate_limits_from_env("ROOM_RATE_LIMITS")?,
+        })
+    }
+}
+
+/// Reads e.g. `nominate=2/60,*=5/30` from the env var.
+fn rate_limits_from_env(key: &str) -> Result<HashMap<String, Rate>> {
+    env::var(key)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let Some((command, rate)) = s.split_once('=') else {
+                bail!("{key} entry {s} is not in the format <command>=<uses>/<seconds>");
+            };
+            let rate = rate
+                .parse()
+                .with_context(|| format!("{key} entry {s} is invalid"))?;
+
+            Ok((command.trim().to_lowercase(), rate))
+        })
+        .collect()
+}
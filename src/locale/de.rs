@@ -0,0 +1,225 @@
+//! German messages, including descriptions and usage examples of commands.
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("welcome", "👋 Hallo!\nIch bin botto :)\n\nSchick eine Nachricht, die mit '{prefix}botto' beginnt, und ich zeige dir, was ich alles kann."),
+    ("no_idea", "Keine Ahnung."),
+    ("throttled", "⏳ Langsam bitte, {command} geht in {seconds} s wieder."),
+    ("did_you_mean", "🤔 {command} kenne ich nicht. Meintest du {suggestions}?\nSchick {help} für eine Liste meiner Befehle."),
+    ("did_you_mean.or", " oder "),
+    // help
+    ("help.header", "🤖 Das sind meine Befehle:"),
+    ("help.source", "🔗 Quellcode des Bots:"),
+    // settings
+    ("settings.forbidden", "Sorry, nur Moderatoren und Admins dieses Raums können meine Einstellungen ändern."),
+    ("settings.unknown", "Die Einstellung '{setting}' kenne ich nicht."),
+    ("settings.toggle_invalid", "Bitte nutze 'an' oder 'aus'."),
+    ("settings.which_command", "Bitte sag mir, welchen Befehl du meinst."),
+    ("settings.unknown_command", "Den Befehl '{command}' kenne ich nicht."),
+    ("settings.not_disableable", "{command} kann nicht ausgeschaltet werden."),
+    ("settings.enabled", "{command} ist an."),
+    ("settings.disabled", "{command} ist aus."),
+    ("settings.prefix_current", "Das Präfix in diesem Raum ist '{prefix}'.\nÄndere es z.B. mit {prefix}botto prefix ?"),
+    ("settings.prefix_invalid", "Ein Präfix hat 1 bis {max} Zeichen ohne Leerzeichen."),
+    ("settings.prefix_set", "Präfix gesetzt. Probier mal {prefix}botto"),
+    ("settings.replies_on", "Antworten sind in diesem Raum an."),
+    ("settings.replies_off", "Antworten sind in diesem Raum aus."),
+    ("settings.replies_turned_on", "Antworten sind jetzt an."),
+    ("settings.replies_turned_off", "Antworten sind jetzt aus."),
+    ("settings.language_current", "In diesem Raum spreche ich Deutsch.\nÄndere das z.B. mit {prefix}botto language en"),
+    ("settings.language_invalid", "Sorry, ich spreche nur {languages}."),
+    ("settings.language_set", "Okay, ab jetzt spreche ich Deutsch."),
+    ("settings.catch_up_current", "Erinnerungen, die ich verpasst habe, während ich offline war, werden {policy}.\nÄndere das z.B. mit {prefix}botto nachholen spät, überspringen oder frist 2h"),
+    ("settings.catch_up_invalid", "Bitte verwende spät, überspringen oder frist mit einer Zeit wie frist 2h."),
+    ("settings.catch_up_set", "Okay, Erinnerungen, die ich verpasst habe, während ich offline war, werden ab jetzt {policy}."),
+    ("settings.quiet_current", "Erinnerungen zu zufälligen Zeiten werden nicht {hours} gesendet.\nSchalte das mit {prefix}botto ruhezeit aus ab"),
+    ("settings.quiet_none", "Dieser Raum hat keine Ruhezeit.\nLege sie z.B. mit {prefix}botto ruhezeit 22:00-7:00 fest"),
+    ("settings.quiet_invalid", "Bitte gib die Ruhezeit wie 22:00-7:00 an, oder aus."),
+    ("settings.quiet_set", "Okay, Erinnerungen zu zufälligen Zeiten werden ab jetzt nicht {hours} gesendet."),
+    ("settings.quiet_off", "Okay, Erinnerungen zu zufälligen Zeiten können ab jetzt zu jeder Stunde gesendet werden."),
+    // coin
+    ("coin.heads", "Kopf"),
+    ("coin.tails", "Zahl"),
+    // conch, one answer per line
+    ("conch.answers", "Eines Tages vielleicht.\nNichts.\nKeins von beiden.\nIch glaube nicht.\nNein.\nJa.\nFrag noch mal.\nVom Rumsitzen kommt man nicht nach oben.\nIch sehe eine neue Soße in deiner Zukunft.\nFrag nächstes Mal.\nFolge dem Seepferdchen."),
+    // choose
+    ("choose.too_few", "Bitte gib mindestens zwei Optionen an."),
+    // nominate
+    ("nominate.nobody", "Ich habe keine aktive Person gefunden :("),
+    // rps, the choices are what users type
+    ("rps.rock", "stein"),
+    ("rps.paper", "papier"),
+    ("rps.scissors", "schere"),
+    ("rps.invalid", "Ungültige Wahl. Bitte wähle 'Stein', 'Papier' oder 'Schere'."),
+    ("rps.lose", "Du verlierst."),
+    ("rps.tie", "Unentschieden."),
+    ("rps.win", "Du gewinnst!"),
+    // reminder
+    ("reminder.created", "Erinnerung {id} erstellt: {reminder}"),
+    ("reminder.invalid", "Sorry, diese Erinnerung verstehe ich nicht.\nDer Befehl {help} gibt dir ein paar Tipps."),
+    ("reminder.none", "Keine Erinnerungen gefunden. Erstelle eine z.B. mit\n\n{example}\n\nOder nutze den Befehl {help} für mehr Infos."),
+    ("reminder.example", "reminder 2 Tage: Müll rausbringen."),
+    ("reminder.list", "⏲️ Erinnerungen:"),
+    ("reminder.deleted", "Erinnerung gelöscht: {reminder}"),
+    ("reminder.all_deleted", "Alle Erinnerungen gelöscht."),
+    ("reminder.all_own_deleted", "Alle deine Erinnerungen gelöscht."),
+    ("reminder.forbidden", "Sorry, nur wer die Erinnerung erstellt hat und Moderatoren dieses Raums können sie ändern oder löschen."),
+    ("reminder.unknown_user", "Ich kenne den Benutzer '{user}' nicht. Bitte verwende z.B. @alice oder @alice:example.org."),
+    ("reminder.which", "Bitte sag mir die ID der Erinnerung, die ich löschen soll, z.B. kfz."),
+    ("reminder.by_id", "Erinnerungen werden über ihre ID wie kfz gewählt, weil sich ihre Nummer in der Liste verschiebt, wenn Erinnerungen erstellt oder gelöscht werden. Die Liste der Erinnerungen zeigt die IDs."),
+    ("reminder.which_edit", "Bitte sag mir die ID der Erinnerung, die ich ändern soll, und was sich ändert, z.B. kfz alle 3h: Wasser trinken, oder kfz Titel: Wasser trinken."),
+    ("reminder.edit_invalid", "Sorry, ich weiß nicht, wie ich diese Änderung verstehen soll."),
+    ("reminder.edited", "Erinnerung geändert: {reminder}"),
+    ("reminder.which_pause", "Bitte sag mir die ID der Erinnerung, die ich pausieren soll, z.B. kfz."),
+    ("reminder.which_resume", "Bitte sag mir die ID der Erinnerung, die ich fortsetzen soll, z.B. kfz."),
+    ("reminder.until_invalid", "Bitte sag mir, wann die Pause endet, mit einem Datum oder einer Uhrzeit in der Zukunft, z.B. bis 07.01.2027."),
+    ("reminder.paused", "⏸️ Erinnerung pausiert: {reminder}"),
+    ("reminder.paused_until", "⏸️ Erinnerung pausiert bis {date} um {time}: {reminder}"),
+    ("reminder.resumed", "▶️ Erinnerung fortgesetzt: {reminder}"),
+    ("reminder.all_paused", "⏸️ {count} Erinnerungen pausiert."),
+    ("reminder.all_paused_until", "⏸️ {count} Erinnerungen pausiert bis {date} um {time}."),
+    ("reminder.all_resumed", "▶️ {count} Erinnerungen fortgesetzt."),
+    ("reminder.list_paused", "pausiert"),
+    ("reminder.list_paused_until", "pausiert bis {date} um {time}"),
+    ("reminder.list_failed", "konnte nicht gesendet werden"),
+    ("reminder.exported", "📤 {count} Erinnerungen exportiert."),
+    ("reminder.import_no_file", "Bitte antworte mit diesem Befehl auf eine hochgeladene Kalenderdatei (.ics)."),
+    ("reminder.import_too_large", "Sorry, die Kalenderdatei ist zu groß, sie darf höchstens {max} KiB haben."),
+    ("reminder.import_invalid", "Sorry, diese Kalenderdatei kann ich nicht lesen."),
+    ("reminder.imported", "📥 {count} Erinnerungen importiert, {skipped} Termine übersprungen."),
+    ("reminder.late", "({span} zu spät)"),
+    ("reminder.title_word", "titel"),
+    ("reminder.every", "alle"),
+    ("reminder.minute", "Minute"),
+    ("reminder.minutes", "Minuten"),
+    ("reminder.hour", "Stunde"),
+    ("reminder.hours", "Stunden"),
+    ("reminder.day", "Tag"),
+    ("reminder.days", "Tage"),
+    ("reminder.second", "Sekunde"),
+    ("reminder.seconds", "Sekunden"),
+    ("reminder.week", "Woche"),
+    ("reminder.weeks", "Wochen"),
+    ("reminder.month", "Monat"),
+    ("reminder.months", "Monate"),
+    ("reminder.year", "Jahr"),
+    ("reminder.years", "Jahre"),
+    ("reminder.past", "Das liegt in der Vergangenheit, bitte wähle einen Zeitpunkt in der Zukunft."),
+    ("reminder.at", "am {date} um {time}"),
+    ("reminder.date_format", "%d.%m.%Y"),
+    ("reminder.next", "nächste: {date} um {time}"),
+    ("reminder.until", "bis {date} um {time}"),
+    ("reminder.until_date", "bis {date}"),
+    ("reminder.times_left", "noch {count} mal"),
+    ("reminder.once_more", "noch einmal"),
+    ("reminder.snooze_word", "schlummern"),
+    ("reminder.snoozed", "💤 Schlummert für {span}: {title}"),
+    // catch-up policies
+    ("catch_up.late", "verspätet gesendet"),
+    ("catch_up.skip", "übersprungen"),
+    ("catch_up.grace", "innerhalb von {window} verspätet gesendet, danach übersprungen"),
+    // hours of random reminders
+    ("hours.between", "zwischen {start} und {end}"),
+    // recurrence
+    ("recurrence.daily", "jeden Tag um {time}"),
+    ("recurrence.weekdays", "jeden Werktag um {time}"),
+    ("recurrence.weekly", "jeden {days} um {time}"),
+    ("recurrence.monthly", "jeden {day} des Monats um {time}"),
+    ("recurrence.last_of_month", "jeden letzten Tag des Monats um {time}"),
+    ("weekday.0", "Montag"),
+    ("weekday.1", "Dienstag"),
+    ("weekday.2", "Mittwoch"),
+    ("weekday.3", "Donnerstag"),
+    ("weekday.4", "Freitag"),
+    ("weekday.5", "Samstag"),
+    ("weekday.6", "Sonntag"),
+    // timezone
+    ("timezone.current", "Deine Zeitzone: {user}\nZeitzone des Raums: {room}\nÄndere deine z.B. mit {prefix}timezone Europe/Berlin"),
+    ("timezone.unset", "nicht gesetzt, die des Raums gilt"),
+    ("timezone.invalid", "Die Zeitzone '{timezone}' kenne ich nicht. Bitte nutze einen Namen wie Europe/Berlin oder America/New_York."),
+    ("timezone.user_set", "Deine Zeitzone ist jetzt {timezone}."),
+    ("timezone.room_set", "Die Zeitzone des Raums ist jetzt {timezone}."),
+    // commands
+    ("botto.description", "ℹ️ - Hilfe und Einstellungen"),
+    ("coinflip.description", "🪙 - Münzwurf"),
+    ("conch.description", "🐚 - magische Miesmuschel"),
+    ("conch.usage", "conch Regnet es morgen?"),
+    ("nominate.description", "👤 - zufällige Person auswählen"),
+    ("roll.description", "🎲 - Würfeln"),
+    ("choose.description", "🔘 - eine Option auswählen"),
+    ("choose.usage", "choose Pizza, Pasta, Sushi"),
+    ("rps.description", "🪨 - Schere, Stein, Papier"),
+    ("rps.usage", "rps stein\nrps papier\nrps schere"),
+    ("reminder.description", "⏲️ - Erinnerung [Sekunden bis Jahre], wiederholt 🔁 oder zufällig 🔀"),
+    ("reminder.usage", "reminder 10 Minuten: Nach dem Ofen sehen\nreminder 2 Stunden: Wäsche ist fertig\nreminder 10 Tage: Rasen mähen\nreminder 1h30m: Pizza ist fertig\nreminder alle 42 Tage: Zum Friseur.\nreminder 1-3 Tage: Alle 1-3 Tage zum Sport\nreminder um 17:30: Standup\nreminder morgen um 8 Uhr: Mama anrufen\nreminder am 24.12.2026 um 18:00: Geschenke\nreminder jeden Montag um 9:00: Standup\nreminder werktags um 17:00: Zeiten eintragen\nreminder jeden 1. des Monats: Miete zahlen\nreminder jeden Tag bis 01.12.2026: Tabletten nehmen\nreminder alle 2 Stunden 5 mal: Dehnen\nreminder alle 1-3 Tage zwischen 9 und 18 Uhr: Zum Sport\nreminder @alice 2 Stunden: Blumen gießen"),
+    ("reminders.description", "⏲️📋 - alle Erinnerungen anzeigen, als .ics exportieren oder importieren"),
+    ("reminders.usage", "reminders\nreminders exportieren\nreminders importieren"),
+    ("editreminder.description", "⏲️✏️ - Erinnerung nach ID ändern"),
+    ("editreminder.usage", "editReminder kfz alle 3h: Wasser trinken\neditReminder kfz alle 3h\neditReminder kfz Titel: Mehr Wasser trinken"),
+    ("pausereminder.description", "⏲️⏸️ - Erinnerung nach ID pausieren"),
+    ("pausereminder.usage", "pauseReminder kfz\npauseReminder kfz bis 07.01.2027"),
+    ("resumereminder.description", "⏲️▶️ - Erinnerung nach ID fortsetzen"),
+    ("pauseallreminders.description", "⏲️⏸️ - alle Erinnerungen pausieren, z.B. im Urlaub"),
+    ("pauseallreminders.usage", "pauseAllReminders\npauseAllReminders bis 07.01.2027"),
+    ("resumeallreminders.description", "⏲️▶️ - alle pausierten Erinnerungen fortsetzen"),
+    ("deletereminder.description", "⏲️🗑️ - Erinnerung nach ID löschen"),
+    ("deleteallreminders.description", "⏲️🗑️ - alle Erinnerungen löschen"),
+    ("timezone.description", "🌍 - Zeitzone für Erinnerungen, deine oder die des Raums"),
+];
+
+/// German words in reminders, lowercase, and what they mean in English.
+pub const REMINDER_KEYWORDS: &[(&str, &str)] = &[
+    ("alle", "every"),
+    ("jede", "every"),
+    ("jeden", "every"),
+    ("jedes", "every"),
+    ("bis", "to"),
+    ("zwischen", "between"),
+    ("mal", "times"),
+    ("minuten", "minutes"),
+    ("min", "minutes"),
+    ("stunde", "hour"),
+    ("stunden", "hours"),
+    ("std", "hours"),
+    ("tag", "day"),
+    ("tage", "days"),
+    ("tagen", "days"),
+    ("t", "days"),
+    ("sekunde", "second"),
+    ("sekunden", "seconds"),
+    ("sek", "seconds"),
+    ("woche", "week"),
+    ("wochen", "weeks"),
+    ("monate", "months"),
+    ("monaten", "months"),
+    ("jahr", "year"),
+    ("jahre", "years"),
+    ("jahren", "years"),
+    ("heute", "today"),
+    ("morgen", "tomorrow"),
+    ("um", "at"),
+    ("am", "on"),
+    ("uhr", ""),
+    ("montag", "monday"),
+    ("montags", "every monday"),
+    ("dienstag", "tuesday"),
+    ("dienstags", "every tuesday"),
+    ("mittwoch", "wednesday"),
+    ("mittwochs", "every wednesday"),
+    ("donnerstag", "thursday"),
+    ("donnerstags", "every thursday"),
+    ("freitag", "friday"),
+    ("freitags", "every friday"),
+    ("samstag", "saturday"),
+    ("samstags", "every saturday"),
+    ("sonntag", "sunday"),
+    ("sonntags", "every sunday"),
+    ("werktag", "weekday"),
+    ("werktags", "every weekday"),
+    ("täglich", "every day"),
+    ("wochenende", "weekend"),
+    ("letzten", "last"),
+    ("des", "of"),
+    ("monats", "month"),
+    ("monat", "month"),
+    ("und", "and"),
+];
@@ -0,0 +1,142 @@
+//! English messages, every other catalog needs the same keys and placeholders.
+//! Command descriptions and usage examples default to the ones in the [`Command`](crate::command::Command) impls.
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    ("welcome", "👋 Hi!\nI'm botto :)\n\nSend a message starting with '{prefix}botto' for a list of things i can do for you."),
+    ("no_idea", "I have no idea."),
+    ("throttled", "⏳ Slow down please, {command} works again in {seconds} s."),
+    ("did_you_mean", "🤔 I don't know {command}. Did you mean {suggestions}?\nSend {help} for a list of my commands."),
+    ("did_you_mean.or", " or "),
+    // help
+    ("help.header", "🤖 Here is a list of my commands:"),
+    ("help.source", "🔗 Bot's source code:"),
+    // settings
+    ("settings.forbidden", "Sorry, only moderators and admins of this room can change my settings."),
+    ("settings.unknown", "I don't know the setting '{setting}'."),
+    ("settings.toggle_invalid", "Please use 'on' or 'off'."),
+    ("settings.which_command", "Please tell me which command you mean."),
+    ("settings.unknown_command", "I don't know the command '{command}'."),
+    ("settings.not_disableable", "{command} can't be disabled."),
+    ("settings.enabled", "{command} enabled."),
+    ("settings.disabled", "{command} disabled."),
+    ("settings.prefix_current", "The prefix in this room is '{prefix}'.\nChange it with e.g. {prefix}botto prefix ?"),
+    ("settings.prefix_invalid", "A prefix must be 1 to {max} characters without spaces."),
+    ("settings.prefix_set", "Prefix set. Try {prefix}botto"),
+    ("settings.replies_on", "Replies are on in this room."),
+    ("settings.replies_off", "Replies are off in this room."),
+    ("settings.replies_turned_on", "Replies turned on."),
+    ("settings.replies_turned_off", "Replies turned off."),
+    ("settings.language_current", "I speak English in this room.\nChange it with e.g. {prefix}botto language de"),
+    ("settings.language_invalid", "Sorry, I only speak {languages}."),
+    ("settings.language_set", "Okay, I'll speak English from now on."),
+    ("settings.catch_up_current", "Reminders missed while I was offline are {policy}.\nChange it with e.g. {prefix}botto catchup late, skip or grace 2h"),
+    ("settings.catch_up_invalid", "Please use late, skip or grace with a time like grace 2h."),
+    ("settings.catch_up_set", "Okay, reminders missed while I was offline are {policy} from now on."),
+    ("settings.quiet_current", "Reminders at random times aren't sent {hours}.\nTurn it off with {prefix}botto quiet off"),
+    ("settings.quiet_none", "This room has no quiet hours.\nSet them with e.g. {prefix}botto quiet 22:00-7:00"),
+    ("settings.quiet_invalid", "Please tell me the quiet hours like 22:00-7:00, or off."),
+    ("settings.quiet_set", "Okay, reminders at random times aren't sent {hours} from now on."),
+    ("settings.quiet_off", "Okay, reminders at random times may be sent at any hour from now on."),
+    // coin
+    ("coin.heads", "Heads"),
+    ("coin.tails", "Tails"),
+    // conch, one answer per line
+    ("conch.answers", "Maybe someday.\nNothing.\nNeither.\nI don't think so.\nNo.\nYes.\nTry asking again.\nYou cannot get to the top by sitting on your bottom.\nI see a new sauce in your future.\nAsk next time.\nFollow the seahorse."),
+    // choose
+    ("choose.too_few", "Please provide at least two options."),
+    // nominate
+    ("nominate.nobody", "Couldn't find an active user :("),
+    // rps, the choices are what users type
+    ("rps.rock", "rock"),
+    ("rps.paper", "paper"),
+    ("rps.scissors", "scissors"),
+    ("rps.invalid", "Invalid choice. Please choose 'rock', 'paper', or 'scissors'."),
+    ("rps.lose", "You lose."),
+    ("rps.tie", "It's a tie."),
+    ("rps.win", "You win!"),
+    // reminder
+    ("reminder.created", "Reminder {id} created: {reminder}"),
+    ("reminder.invalid", "Sorry, I don't know how to parse that reminder.\nUse the {help} command to get some hints."),
+    ("reminder.none", "No reminders found. Create one with e.g.\n\n{example}\n\nOr use the {help} command to get more info."),
+    ("reminder.example", "reminder 2 days: Take out the trash."),
+    ("reminder.list", "⏲️ Reminders:"),
+    ("reminder.deleted", "Reminder deleted: {reminder}"),
+    ("reminder.all_deleted", "All reminders deleted."),
+    ("reminder.all_own_deleted", "All your reminders deleted."),
+    ("reminder.forbidden", "Sorry, only the reminder's creator and moderators of this room can change or delete it."),
+    ("reminder.unknown_user", "I don't know the user '{user}'. Please use e.g. @alice or @alice:example.org."),
+    ("reminder.which", "Please tell me the id of the reminder to delete, e.g. kfz."),
+    ("reminder.by_id", "Reminders are chosen by their id like kfz, because their number in the list shifts when reminders are created or deleted. The list of reminders shows the ids."),
+    ("reminder.which_edit", "Please tell me the id of the reminder to edit and what to change, e.g. kfz every 3h: Drink water, or kfz title: Drink water."),
+    ("reminder.edit_invalid", "Sorry, I don't know how to parse that change."),
+    ("reminder.edited", "Reminder changed: {reminder}"),
+    ("reminder.which_pause", "Please tell me the id of the reminder to pause, e.g. kfz."),
+    ("reminder.which_resume", "Please tell me the id of the reminder to resume, e.g. kfz."),
+    ("reminder.until_invalid", "Please tell me when the pause ends with a date or time in the future, e.g. until 2026-01-07."),
+    ("reminder.paused", "⏸️ Reminder paused: {reminder}"),
+    ("reminder.paused_until", "⏸️ Reminder paused until {date} at {time}: {reminder}"),
+    ("reminder.resumed", "▶️ Reminder resumed: {reminder}"),
+    ("reminder.all_paused", "⏸️ {count} reminders paused."),
+    ("reminder.all_paused_until", "⏸️ {count} reminders paused until {date} at {time}."),
+    ("reminder.all_resumed", "▶️ {count} reminders resumed."),
+    ("reminder.list_paused", "paused"),
+    ("reminder.list_paused_until", "paused until {date} at {time}"),
+    ("reminder.list_failed", "couldn't be sent"),
+    ("reminder.exported", "📤 {count} reminders exported."),
+    ("reminder.import_no_file", "Please reply with this command to an uploaded calendar file (.ics)."),
+    ("reminder.import_too_large", "Sorry, the calendar file is too large, it may have at most {max} KiB."),
+    ("reminder.import_invalid", "Sorry, I can't read this calendar file."),
+    ("reminder.imported", "📥 {count} reminders imported, {skipped} events skipped."),
+    ("reminder.late", "(late by {span})"),
+    ("reminder.title_word", "title"),
+    ("reminder.every", "every"),
+    ("reminder.minute", "minute"),
+    ("reminder.minutes", "minutes"),
+    ("reminder.hour", "hour"),
+    ("reminder.hours", "hours"),
+    ("reminder.day", "day"),
+    ("reminder.days", "days"),
+    ("reminder.second", "second"),
+    ("reminder.seconds", "seconds"),
+    ("reminder.week", "week"),
+    ("reminder.weeks", "weeks"),
+    ("reminder.month", "month"),
+    ("reminder.months", "months"),
+    ("reminder.year", "year"),
+    ("reminder.years", "years"),
+    ("reminder.past", "That's in the past, please pick a time in the future."),
+    ("reminder.at", "on {date} at {time}"),
+    ("reminder.date_format", "%Y-%m-%d"),
+    ("reminder.next", "next: {date} at {time}"),
+    ("reminder.until", "until {date} at {time}"),
+    ("reminder.until_date", "until {date}"),
+    ("reminder.times_left", "{count} more times"),
+    ("reminder.once_more", "once more"),
+    ("reminder.snooze_word", "snooze"),
+    ("reminder.snoozed", "💤 Snoozed for {span}: {title}"),
+    // catch-up policies
+    ("catch_up.late", "sent late"),
+    ("catch_up.skip", "skipped"),
+    ("catch_up.grace", "sent late within {window}, skipped after"),
+    // hours of random reminders
+    ("hours.between", "between {start} and {end}"),
+    // recurrence
+    ("recurrence.daily", "every day at {time}"),
+    ("recurrence.weekdays", "every weekday at {time}"),
+    ("recurrence.weekly", "every {days} at {time}"),
+    ("recurrence.monthly", "every {day} of the month at {time}"),
+    ("recurrence.last_of_month", "every last day of the month at {time}"),
+    ("weekday.0", "Monday"),
+    ("weekday.1", "Tuesday"),
+    ("weekday.2", "Wednesday"),
+    ("weekday.3", "Thursday"),
+    ("weekday.4", "Friday"),
+    ("weekday.5", "Saturday"),
+    ("weekday.6", "Sunday"),
+    // timezone
+    ("timezone.current", "Your timezone: {user}\nThe room's timezone: {room}\nChange yours with e.g. {prefix}timezone Europe/Berlin"),
+    ("timezone.unset", "not set, the room's applies"),
+    ("timezone.invalid", "I don't know the timezone '{timezone}'. Please use a name like Europe/Berlin or America/New_York."),
+    ("timezone.user_set", "Your timezone is now {timezone}."),
+    ("timezone.room_set", "The room's timezone is now {timezone}."),
+];
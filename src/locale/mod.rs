@@ -0,0 +1,216 @@
+use crate::prelude::*;
+use std::str::FromStr;
+
+mod de;
+mod en;
+
+/// Language of the bot's responses, English unless a room or the deployment picks another one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    De,
+}
+
+impl Language {
+    pub const ALL: [Self; 2] = [Self::En, Self::De];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::De => "de",
+        }
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::En => en::MESSAGES,
+            Self::De => de::MESSAGES,
+        }
+    }
+
+    /// Localized words in reminders and their English counterparts.
+    fn reminder_keywords(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::En => &[],
+            Self::De => de::REMINDER_KEYWORDS,
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "en" | "english" | "englisch" => Ok(Self::En),
+            "de" | "german" | "deutsch" => Ok(Self::De),
+            _ => bail!("unknown language {s}"),
+        }
+    }
+}
+
+/// Looks up a message in the language's catalog, falling back to English.
+pub fn lookup(lang: Language, key: &str) -> Option<&'static str> {
+    let find = |catalog: &'static [(&'static str, &'static str)]| {
+        catalog.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    };
+
+    find(lang.catalog()).or_else(|| find(en::MESSAGES))
+}
+
+/// The message for the key, or a shrug if no catalog has it.
+pub fn tr(lang: Language, key: &str) -> &'static str {
+    lookup(lang, key).unwrap_or_else(|| {
+        error!("message {key} is missing in the catalogs");
+        "🤷"
+    })
+}
+
+/// The message for the key, with `{name}` placeholders replaced by the arguments.
+pub fn trf(lang: Language, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    fill(tr(lang, key).to_string(), args)
+}
+
+/// Like [`trf`] for HTML bodies, the message is escaped, the arguments are expected to be HTML already.
+pub fn trf_html(lang: Language, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    fill(crate::command::response::escape(tr(lang, key)), args)
+}
+
+/// E.g. `1st` in English and `1.` in German.
+pub fn ordinal(lang: Language, n: i32) -> String {
+    match lang {
+        Language::En => {
+            let suffix = match (n % 10, n % 100) {
+                (_, 11..=13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+            format!("{n}{suffix}")
+        }
+        Language::De => format!("{n}."),
+    }
+}
+
+fn fill(mut message: String, args: &[(&str, &dyn Display)]) -> String {
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+
+    message
+}
+
+/// Replaces localized keywords before the title of a reminder with English ones, e.g. `alle 2 Tage` with `every 2 days`.
+pub fn normalize_reminder(lang: Language, text: &str) -> String {
+    let keywords = lang.reminder_keywords();
+    let (spec, title) = match crate::command::reminder::split_title(text) {
+        Some((spec, title)) => (spec, Some(title)),
+        None => (text, None),
+    };
+
+    let mut res = String::with_capacity(text.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, res: &mut String| {
+        let lower = word.to_lowercase();
+        match keywords.iter().find(|(k, _)| *k == lower) {
+            Some((_, english)) => res.push_str(english),
+            None => res.push_str(word),
+        }
+        word.clear();
+    };
+    for c in spec.chars() {
+        if c.is_alphabetic() {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut res);
+            res.push(c);
+        }
+    }
+    flush(&mut word, &mut res);
+
+    if let Some(title) = title {
+        res.push(':');
+        res.push_str(title);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(message: &str) -> BTreeSet<&str> {
+        message
+            .split('{')
+            .skip(1)
+            .filter_map(|s| s.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    pub fn catalogs_are_complete() {
+        for lang in Language::ALL {
+            for (key, message) in en::MESSAGES {
+                let Some((_, translated)) = lang.catalog().iter().find(|(k, _)| k == key) else {
+                    panic!("{key} is missing in {}", lang.code());
+                };
+                assert_eq!(
+                    placeholders(message),
+                    placeholders(translated),
+                    "placeholders of {key} differ in {}",
+                    lang.code()
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn translate() {
+        assert_eq!(tr(Language::De, "coin.heads"), "Kopf");
+        assert_eq!(
+            trf(
+                Language::En,
+                "reminder.deleted",
+                &[("reminder", &"2 days: x")]
+            ),
+            "Reminder deleted: 2 days: x"
+        );
+        assert_eq!(
+            trf_html(
+                Language::En,
+                "throttled",
+                &[("command", &"<code>!r</code>"), ("seconds", &3)]
+            ),
+            "⏳ Slow down please, <code>!r</code> works again in 3 s."
+        );
+    }
+
+    #[test]
+    pub fn normalize_german_reminder() {
+        assert_eq!(
+            normalize_reminder(Language::De, "alle 2 Tage: Alle Tage Müll"),
+            "every 2 days: Alle Tage Müll"
+        );
+        assert_eq!(
+            normalize_reminder(Language::De, "Alle 1 bis 3 Stunden: Wasser trinken"),
+            "every 1 to 3 hours: Wasser trinken"
+        );
+        assert_eq!(
+            normalize_reminder(Language::De, "10 min: Ofen"),
+            "10 minutes: Ofen"
+        );
+        assert_eq!(
+            normalize_reminder(Language::De, "morgen um 8:30 Uhr: Bäcker"),
+            "tomorrow at 8:30 : Bäcker"
+        );
+        assert_eq!(
+            normalize_reminder(Language::En, "every 2 days: x"),
+            "every 2 days: x"
+        );
+    }
+}
@@ -1,6 +1,9 @@
 mod command;
+mod config;
+mod locale;
 mod matrix;
 mod prelude;
+mod settings;
 use dotenv::dotenv;
 
 use prelude::*;
@@ -17,12 +20,17 @@ async fn main() -> Result<()> {
     dotenv().ok();
     tracing_subscriber::fmt::init();
 
+    let config = config::Config::from_env().unwrap_or_else(|err| {
+        error!("💥 error in reading config: {err:?}");
+        std::process::exit(1);
+    });
+
     let db = setup_db().await.unwrap_or_else(|err| {
         error!("💥 error in setting up db: {err:?}");
         std::process::exit(1);
     });
 
-    match matrix::start_client(db).await {
+    match matrix::start_client(db, config).await {
         Ok(_) => {
             info!("🏁 done");
             Ok(())
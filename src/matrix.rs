@@ -1,14 +1,27 @@
-use crate::{command::*, prelude::*};
+use crate::{
+    command::*,
+    config::Config,
+    locale::trf,
+    prelude::*,
+    settings::{self, RoomSettings},
+};
 
 use matrix_sdk::{
     config::SyncSettings,
     matrix_auth::MatrixSession,
     ruma::{
-        events::room::{
-            member::StrippedRoomMemberEvent,
-            message::{MessageType, OriginalSyncRoomMessageEvent},
+        events::{
+            reaction::OriginalSyncReactionEvent,
+            room::{
+                member::StrippedRoomMemberEvent,
+                message::{
+                    sanitize::remove_plain_reply_fallback, AddMentions, ForwardThread, MessageType,
+                    OriginalRoomMessageEvent, OriginalSyncRoomMessageEvent, Relation,
+                    ReplacementMetadata,
+                },
+            },
         },
-        OwnedUserId,
+        EventId, OwnedUserId,
     },
     Error, LoopCtrl, Room, RoomState,
 };
@@ -16,11 +29,14 @@ use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use serde::{Deserialize, Serialize};
 use std::{
     path::{Path, PathBuf},
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 use tokio::fs;
 
-pub async fn start_client(db: Surreal<Any>) -> Result<()> {
+pub async fn start_client(db: Surreal<Any>, config: Config) -> Result<()> {
     // create or restore session
 
     let sqlite_file = Path::new("client_data/sqlite_db");
@@ -104,10 +120,14 @@ pub async fn start_client(db: Surreal<Any>) -> Result<()> {
 
     info!("🦻 client listens to updates");
 
+    let config = Arc::new(config);
+    let registry = Arc::new(Registry::new());
+
     let db_clone = db.clone();
     let client_clone = client.clone();
 
-    let reminder_notifier = reminder::notify(db_clone, client_clone);
+    let reminder_notifier =
+        reminder::notify(db_clone, client_clone, registry.clone(), config.clone());
 
     let client_ref = &client;
     let password_ref = &password;
@@ -115,9 +135,34 @@ pub async fn start_client(db: Surreal<Any>) -> Result<()> {
     let perform_cross_sign_ref = &perform_cross_sign;
 
     let sync_client = async {
-        client.add_event_handler(on_stripped_state_member);
+        let config_clone = config.clone();
+        client.add_event_handler(move |room_member, client, room| {
+            on_stripped_state_member(room_member, client, room, config_clone.clone())
+        });
         let db_clone = db.clone();
-        client.add_event_handler(|event, room| on_room_message(event, room, db_clone));
+        let registry_clone = registry.clone();
+        let config_clone = config.clone();
+        client.add_event_handler(move |event, room, client| {
+            on_room_message(
+                event,
+                room,
+                client,
+                db_clone.clone(),
+                registry_clone.clone(),
+                config_clone.clone(),
+            )
+        });
+        let db_clone = db.clone();
+        client.add_event_handler(move |event, room, client| {
+            on_reaction(
+                event,
+                room,
+                client,
+                db_clone.clone(),
+                registry.clone(),
+                config.clone(),
+            )
+        });
 
         let Err(err) = client
             .sync_with_result_callback(sync_settings, |sync_result| async move {
@@ -172,6 +217,7 @@ async fn on_stripped_state_member(
     room_member: StrippedRoomMemberEvent,
     client: Client,
     room: Room,
+    config: Arc<Config>,
 ) -> Result<()> {
     let Some(user_id) = client.user_id() else {
         bail!("user_id is not set");
@@ -201,7 +247,11 @@ async fn on_stripped_state_member(
             }
         }
         info!("👋 joined room {}", room.room_id());
-        let welcome_message = RoomMessageEventContent::text_plain("👋 Hi!\nI'm botto :)\n\nSend a message starting with '!botto' for a list of things i can do for you.");
+        let welcome_message = RoomMessageEventContent::text_plain(trf(
+            config.language,
+            "welcome",
+            &[("prefix", &config.prefix)],
+        ));
         let _ = room
             .send(welcome_message)
             .await
@@ -221,7 +271,10 @@ async fn on_stripped_state_member(
 async fn on_room_message(
     event: OriginalSyncRoomMessageEvent,
     room: Room,
+    client: Client,
     db: Surreal<Any>,
+    registry: Arc<Registry>,
+    config: Arc<Config>,
 ) -> Result<()> {
     trace!("message from room {}, event: {:?}", room.room_id(), event);
 
@@ -229,62 +282,212 @@ async fn on_room_message(
         debug!("ignoring message from room {}, not joined", room.room_id());
         return Ok(());
     }
-    let MessageType::Text(text_content) = event.content.msgtype else {
+    // the bot's own messages, e.g. fired reminders, are never commands, so two bots can't ping-pong
+    if client.user_id() == Some(&*event.sender) {
+        trace!("ignoring own message in room {}", room.room_id());
+        return Ok(());
+    }
+    if config.ignored_users.contains(&event.sender) {
+        debug!(
+            "ignoring message from {} in room {}",
+            event.sender,
+            room.room_id()
+        );
+        return Ok(());
+    }
+
+    // an edit carries the new text in `m.new_content` and points to the original event
+    let (msgtype, edited) = match &event.content.relates_to {
+        Some(Relation::Replacement(replacement)) => (
+            &replacement.new_content.msgtype,
+            Some(replacement.event_id.clone()),
+        ),
+        _ => (&event.content.msgtype, None),
+    };
+    let MessageType::Text(text_content) = msgtype else {
         debug!("ignoring non-text message from room {}", room.room_id());
         return Ok(());
     };
+    // only the sender of a command may edit it, anyone else could rewrite its answer
+    let edited_original = match &edited {
+        Some(event_id) => match fetch_message(&room, event_id).await {
+            Ok(original) if original.sender == event.sender => Some(original),
+            Ok(original) => {
+                debug!(
+                    "ignoring edit of {event_id} by {}, it was sent by {}",
+                    event.sender, original.sender
+                );
+                return Ok(());
+            }
+            Err(err) => {
+                warn!("fails to get edited event {event_id}, ignoring the edit, error: {err:?}");
+                return Ok(());
+            }
+        },
+        None => None,
+    };
 
-    let text = text_content.body.trim();
-    let cmd = text.to_lowercase();
-    // help
-    let resp = if cmd.starts_with("!botto") {
-        Some(help::text())
-    // conch
-    } else if cmd.starts_with("!conch") {
-        Some(conch::answer())
-    // coinflip
-    } else if cmd.starts_with("!coinflip") {
-        Some(coin::flip())
-    // nominate
-    } else if cmd.starts_with("!nominate") {
-        Some(nominate::user(&room).await)
-    // choose
-    } else if cmd.starts_with("!choose") {
-        Some(choose::option(text.replace("!choose", "")))
-    // rock, paper, scissors
-    } else if cmd.starts_with("!rps") {
-        Some(rps::play(text.replace("!rps", "")))
-    // roll dice
-    } else if cmd.starts_with("!r ") {
-        Some(roll::dice(&cmd))
-    // reminder
-    } else if cmd.starts_with("!reminder ") {
-        Some(reminder::new(room.room_id(), text, &db).await?)
-    } else if cmd.starts_with("!reminders") {
-        Some(reminder::list(room.room_id(), &db).await?)
-    } else if cmd.starts_with("!deleteallreminder") {
-        Some(reminder::delete_all(room.room_id(), &db).await?)
-    } else if cmd.starts_with("!deletereminder") {
-        reminder::delete(room.room_id(), text, &db).await?
-    // no command
-    } else {
-        trace!(
-            "ignoring message from room {}, no matching command",
-            room.room_id()
-        );
-        None
+    let settings = RoomSettings::load(room.room_id(), &db).await?;
+    let prefix = settings.prefix.as_deref().unwrap_or(&config.prefix);
+    let lang = settings.language.unwrap_or(config.language);
+
+    // replies quote the message they answer, which is no part of the command
+    let (body, reply_to) = match (&event.content.relates_to, &edited) {
+        (Some(Relation::Reply { in_reply_to }), None) => (
+            remove_plain_reply_fallback(&text_content.body),
+            Some(&*in_reply_to.event_id),
+        ),
+        _ => (text_content.body.as_str(), None),
+    };
+
+    // replying e.g. `snooze 1h` to a fired reminder snoozes it
+    let ctx = Ctx {
+        room: &room,
+        db: &db,
+        registry: &registry,
+        config: &config,
+        settings: &settings,
+        sender: &event.sender,
+        edit: edited.is_some(),
+        reply_to,
+        prefix,
+        lang,
+    };
+    let snooze = reply_to.zip(reminder::parse_snooze(body, lang));
+    let resp = match snooze {
+        // snoozes are checked like `!reminder`, so they stop when it's disabled or throttled
+        Some((reply_to, span)) => match registry.admit(&ctx, "reminder") {
+            Admission::Run => {
+                let tz = settings::timezone(&event.sender, &settings, &db).await?;
+                let snoozed =
+                    reminder::snooze(room.room_id(), reply_to, span, &event.sender, tz, lang, &db)
+                        .await?;
+                match snoozed {
+                    Some(text) => Some(Response::from(text)),
+                    None => registry.dispatch(&ctx, body).await?,
+                }
+            }
+            Admission::Refused(resp) => resp,
+        },
+        None => registry.dispatch(&ctx, body).await?,
+    };
+
+    let Some(resp) = resp else {
+        return Ok(());
     };
 
-    if let Some(resp) = resp {
-        let content = RoomMessageEventContent::text_plain(resp);
+    let room_id = room.room_id().to_owned();
+    let (request_event_id, original) = match edited {
+        Some(event_id) => (event_id, edited_original),
+        None => (
+            event.event_id.clone(),
+            Some(event.into_full_event(room_id.clone())),
+        ),
+    };
+    let original = original.as_ref().filter(|_| settings.replies());
+
+    let mut content = resp.into_content();
+    if let Some(previous) = response::find_sent(&room_id, &request_event_id, &db).await? {
+        debug!("editing response {previous} to edited command {request_event_id}");
+        content = content.make_replacement(ReplacementMetadata::new(previous, None), original);
         room.send(content)
+            .await
+            .context("fails to send edited text message")?;
+    } else {
+        if let Some(original) = original {
+            content = content.make_reply_to(original, ForwardThread::Yes, AddMentions::Yes);
+        }
+        let sent = room
+            .send(content)
             .await
             .context("fauls to send text message")?;
+        response::save_sent(&room_id, &request_event_id, &sent.event_id, &db).await?;
+    }
+
+    Ok(())
+}
+
+/// Reacting with ⏰ to a fired reminder snoozes it for 15 minutes.
+async fn on_reaction(
+    event: OriginalSyncReactionEvent,
+    room: Room,
+    client: Client,
+    db: Surreal<Any>,
+    registry: Arc<Registry>,
+    config: Arc<Config>,
+) -> Result<()> {
+    trace!("reaction in room {}, event: {:?}", room.room_id(), event);
+
+    let annotation = &event.content.relates_to;
+    if room.state() != RoomState::Joined
+        || client.user_id() == Some(&*event.sender)
+        || config.ignored_users.contains(&event.sender)
+        || annotation.key.trim_end_matches('\u{fe0f}') != "⏰"
+    {
+        return Ok(());
     }
 
+    let settings = RoomSettings::load(room.room_id(), &db).await?;
+    let lang = settings.language.unwrap_or(config.language);
+    let ctx = Ctx {
+        room: &room,
+        db: &db,
+        registry: &registry,
+        config: &config,
+        settings: &settings,
+        sender: &event.sender,
+        edit: false,
+        reply_to: None,
+        prefix: settings.prefix.as_deref().unwrap_or(&config.prefix),
+        lang,
+    };
+    // snoozes are checked like `!reminder`, so they stop when it's disabled or throttled
+    if let Admission::Refused(resp) = registry.admit(&ctx, "reminder") {
+        if let Some(resp) = resp {
+            room.send(resp.into_content())
+                .await
+                .context("fails to send throttle message")?;
+        }
+        return Ok(());
+    }
+
+    let tz = settings::timezone(&event.sender, &settings, &db).await?;
+    let Some(text) = reminder::snooze(
+        room.room_id(),
+        &annotation.event_id,
+        reminder::DEFAULT_SNOOZE,
+        &event.sender,
+        tz,
+        lang,
+        &db,
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+
+    room.send(RoomMessageEventContent::text_plain(text))
+        .await
+        .context("fails to send snooze message")?;
+
     Ok(())
 }
 
+pub(crate) async fn fetch_message(
+    room: &Room,
+    event_id: &EventId,
+) -> Result<OriginalRoomMessageEvent> {
+    let event = room
+        .event(event_id)
+        .await
+        .context("fails to get event")?
+        .event
+        .deserialize_as::<OriginalSyncRoomMessageEvent>()
+        .context("fails to deserialize message event")?;
+
+    Ok(event.into_full_event(room.room_id().to_owned()))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ClientSession {
     /// The URL of the homeserver of the user.
@@ -0,0 +1,181 @@
+use crate::{
+    command::{catch_up::CatchUp, hours::Hours},
+    locale::Language,
+    prelude::*,
+};
+use chrono_tz::Tz;
+use matrix_sdk::{ruma::UserId, Room};
+
+pub const MAX_PREFIX_LEN: usize = 8;
+
+/// Power level needed to change a room's settings, moderators have 50 by default.
+pub const SETTINGS_POWER_LEVEL: i64 = 50;
+
+/// Per room overrides of the deployment wide [`Config`](crate::config::Config).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoomSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Whether responses are sent as replies to the command, defaults to `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replies: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<Language>,
+    /// Timezone for members without their own, UTC if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>,
+    /// What happens to reminders missed while the bot was offline, sent late if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catch_up: Option<CatchUp>,
+    /// Hours on the room's clock in which reminders at random times aren't sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<Hours>,
+    /// Names of commands disabled in the room, stored in the `disabled_command` table.
+    #[serde(skip)]
+    pub disabled_commands: Vec<String>,
+}
+
+impl RoomSettings {
+    pub async fn load(room_id: &RoomId, db: &Surreal<Any>) -> Result<Self> {
+        let mut res = db
+            .query("select * from type::thing('room_settings', $room_id)")
+            .query("select value command from disabled_command where room_id = $room_id")
+            .bind(("room_id", room_id.to_string()))
+            .await?;
+
+        let settings: Option<Self> = res.take(0).context("fails to select room settings")?;
+        let mut settings = settings.unwrap_or_default();
+        settings.disabled_commands = res.take(1).context("fails to select disabled commands")?;
+
+        Ok(settings)
+    }
+
+    pub async fn save(&self, room_id: &RoomId, db: &Surreal<Any>) -> Result<()> {
+        let mut record = serde_json::to_value(self).context("fails to serialize room settings")?;
+        record["room_id"] = room_id.to_string().into();
+
+        db.query("update type::thing('room_settings', $room_id) content $record")
+            .bind(("room_id", room_id.to_string()))
+            .bind(("record", record))
+            .await?
+            .check()
+            .context("fails to update room settings")?;
+
+        info!("⚙️ settings of room {room_id} updated: {self:?}");
+
+        Ok(())
+    }
+
+    pub fn replies(&self) -> bool {
+        self.replies.unwrap_or(true)
+    }
+
+    pub fn is_enabled(&self, command: &str) -> bool {
+        !self.disabled_commands.iter().any(|c| c == command)
+    }
+
+    pub async fn set_enabled(
+        room_id: &RoomId,
+        command: &str,
+        enabled: bool,
+        user_id: &UserId,
+        db: &Surreal<Any>,
+    ) -> Result<()> {
+        let query = if enabled {
+            "delete type::thing('disabled_command', [$room_id, $command])"
+        } else {
+            "update type::thing('disabled_command', [$room_id, $command]) content { room_id: $room_id, command: $command, disabled_by: $user_id }"
+        };
+
+        db.query(query)
+            .bind(("room_id", room_id.to_string()))
+            .bind(("command", command))
+            .bind(("user_id", user_id.to_string()))
+            .await?
+            .check()
+            .context("fails to update disabled command")?;
+
+        info!(
+            "⚙️ command {command} {} in room {room_id} by {user_id}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+
+        Ok(())
+    }
+}
+
+/// Per user settings that apply in every room.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>,
+}
+
+impl UserSettings {
+    pub async fn load(user_id: &UserId, db: &Surreal<Any>) -> Result<Self> {
+        let settings: Option<Self> = db
+            .query("select * from type::thing('user_settings', $user_id)")
+            .bind(("user_id", user_id.to_string()))
+            .await?
+            .take(0)
+            .context("fails to select user settings")?;
+
+        Ok(settings.unwrap_or_default())
+    }
+
+    pub async fn save(&self, user_id: &UserId, db: &Surreal<Any>) -> Result<()> {
+        let mut record = serde_json::to_value(self).context("fails to serialize user settings")?;
+        record["user_id"] = user_id.to_string().into();
+
+        db.query("update type::thing('user_settings', $user_id) content $record")
+            .bind(("user_id", user_id.to_string()))
+            .bind(("record", record))
+            .await?
+            .check()
+            .context("fails to update user settings")?;
+
+        info!("⚙️ settings of user {user_id} updated: {self:?}");
+
+        Ok(())
+    }
+}
+
+/// The user's timezone, falling back to the room's and then to UTC.
+pub async fn timezone(user_id: &UserId, room: &RoomSettings, db: &Surreal<Any>) -> Result<Tz> {
+    let user = UserSettings::load(user_id, db).await?;
+
+    Ok(user.timezone.or(room.timezone).unwrap_or(Tz::UTC))
+}
+
+/// Whether the user's power level in the room allows changing its settings.
+pub async fn may_change(room: &Room, user_id: &UserId) -> Result<bool> {
+    let Some(member) = room
+        .get_member(user_id)
+        .await
+        .context("fails to get room member")?
+    else {
+        return Ok(false);
+    };
+
+    Ok(has_settings_power(member.power_level()))
+}
+
+/// Whether a member with the power level may change settings and others' reminders, like moderators.
+pub fn has_settings_power(power_level: i64) -> bool {
+    power_level >= SETTINGS_POWER_LEVEL
+}
+
+pub fn is_valid_prefix(prefix: &str) -> bool {
+    !prefix.is_empty()
+        && prefix.chars().count() <= MAX_PREFIX_LEN
+        && !prefix.chars().any(char::is_whitespace)
+}
+
+/// Parses `on`/`off` style values of toggle settings.
+pub fn parse_toggle(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "on" | "yes" | "true" | "enable" | "an" | "ja" => Some(true),
+        "off" | "no" | "false" | "disable" | "aus" | "nein" => Some(false),
+        _ => None,
+    }
+}